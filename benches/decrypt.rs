@@ -9,7 +9,7 @@ use dusk_bls12_381::BlsScalar;
 use dusk_jubjub::{JubJubAffine, JubJubScalar, GENERATOR_EXTENDED};
 use dusk_plonk::prelude::Error as PlonkError;
 use dusk_plonk::prelude::*;
-use dusk_poseidon::{decrypt, decrypt_gadget, encrypt};
+use dusk_poseidon::{decrypt, decrypt_gadget, encrypt, Nonce};
 use ff::Field;
 use once_cell::sync::Lazy;
 use rand::rngs::StdRng;
@@ -40,10 +40,10 @@ impl DecryptionCircuit {
             .iter_mut()
             .for_each(|s| *s = BlsScalar::random(&mut *rng));
         let shared_secret =
-            GENERATOR_EXTENDED * &JubJubScalar::random(&mut *rng);
+            GENERATOR_EXTENDED * JubJubScalar::random(&mut *rng);
         let shared_secret = shared_secret.into();
         let nonce = BlsScalar::random(&mut *rng);
-        let cipher = encrypt(&message, &shared_secret, &nonce)
+        let cipher = encrypt(message, shared_secret, &nonce.into())
             .expect("encryption should not fail");
 
         Self {
@@ -101,12 +101,13 @@ fn bench_decryption(c: &mut Criterion) {
     let mut proof = Proof::default();
 
     // Benchmark native cipher decryption
+    let nonce = Nonce::from(circuit.nonce);
     c.bench_function("decrypt 2 BlsScalar", |b| {
         b.iter(|| {
             _ = decrypt(
                 black_box(&circuit.cipher),
-                black_box(&circuit.shared_secret),
-                black_box(&circuit.nonce),
+                black_box(circuit.shared_secret),
+                black_box(&nonce),
             );
         })
     });
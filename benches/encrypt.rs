@@ -9,7 +9,7 @@ use dusk_bls12_381::BlsScalar;
 use dusk_jubjub::{JubJubAffine, JubJubScalar, GENERATOR_EXTENDED};
 use dusk_plonk::prelude::Error as PlonkError;
 use dusk_plonk::prelude::*;
-use dusk_poseidon::{encrypt, encrypt_gadget};
+use dusk_poseidon::{encrypt, encrypt_gadget, Nonce};
 use ff::Field;
 use once_cell::sync::Lazy;
 use rand::rngs::StdRng;
@@ -40,7 +40,7 @@ impl EncryptionCircuit {
             .iter_mut()
             .for_each(|s| *s = BlsScalar::random(&mut *rng));
         let shared_secret =
-            GENERATOR_EXTENDED * &JubJubScalar::random(&mut *rng);
+            GENERATOR_EXTENDED * JubJubScalar::random(&mut *rng);
         let nonce = BlsScalar::random(&mut *rng);
 
         Self {
@@ -78,7 +78,7 @@ impl Circuit for EncryptionCircuit {
 
         // encrypt the message with the gadget
         let _cipher_result =
-            encrypt_gadget(composer, &message_wit, &secret_wit, &nonce_wit)
+            encrypt_gadget(composer, message_wit, &secret_wit, &nonce_wit)
                 .expect("encryption should pass");
 
         Ok(())
@@ -96,13 +96,14 @@ fn bench_encryption(c: &mut Criterion) {
     let public_inputs = Vec::new();
     let mut proof = Proof::default();
 
-    // Benchmark native cipher decryption
+    // Benchmark native cipher encryption
+    let nonce = Nonce::from(circuit.nonce);
     c.bench_function("encrypt 2 BlsScalar", |b| {
         b.iter(|| {
             let _ = encrypt(
                 black_box(&circuit.message),
-                black_box(&circuit.shared_secret),
-                black_box(&circuit.nonce),
+                black_box(circuit.shared_secret),
+                black_box(&nonce),
             );
         })
     });
@@ -6,7 +6,9 @@
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use dusk_plonk::prelude::*;
-use dusk_poseidon::{Domain, Hash, HashGadget, HADES_WIDTH};
+use dusk_poseidon::{
+    compress, compress_gadget, Domain, Hash, HashGadget, HADES_WIDTH,
+};
 use ff::Field;
 use rand::rngs::StdRng;
 use rand::SeedableRng;
@@ -90,9 +92,68 @@ fn bench_sponge(c: &mut Criterion) {
     });
 }
 
+#[derive(Default)]
+struct CompressCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+    output: BlsScalar,
+}
+
+impl Circuit for CompressCircuit {
+    fn circuit(&self, composer: &mut Composer) -> Result<(), Error> {
+        let a = composer.append_witness(self.a);
+        let b = composer.append_witness(self.b);
+
+        let output = compress_gadget(composer, a, b);
+        composer.assert_equal_constant(output, 0, Some(self.output));
+
+        Ok(())
+    }
+}
+
+// Benchmark for compressing 2 BlsScalar, one permutation
+fn bench_compress(c: &mut Criterion) {
+    // Prepare benchmarks and initialize variables
+    let label = b"compress benchmark";
+    let mut rng = StdRng::seed_from_u64(0xc10d);
+    let pp = PublicParameters::setup(1 << CAPACITY, &mut rng).unwrap();
+    let (prover, verifier) = Compiler::compile::<CompressCircuit>(&pp, label)
+        .expect("Circuit should compile successfully");
+    let mut proof = Proof::default();
+    let a = BlsScalar::random(&mut rng);
+    let b = BlsScalar::random(&mut rng);
+    let output = compress(&a, &b);
+    let circuit = CompressCircuit { a, b, output };
+
+    // Benchmark compression native
+    c.bench_function("compress 2 BlsScalar", |bench| {
+        bench.iter(|| {
+            let _ = compress(black_box(&circuit.a), black_box(&circuit.b));
+        })
+    });
+
+    // Benchmark proof creation
+    c.bench_function("compress 2 BlsScalar proof generation", |bench| {
+        bench.iter(|| {
+            (proof, _) = prover
+                .prove(&mut rng, black_box(&circuit))
+                .expect("Proof generation should succeed");
+        })
+    });
+
+    // Benchmark proof verification
+    c.bench_function("compress 2 BlsScalar proof verification", |bench| {
+        bench.iter(|| {
+            verifier
+                .verify(black_box(&proof), &[output])
+                .expect("Proof verification should succeed");
+        })
+    });
+}
+
 criterion_group! {
     name = benches;
     config = Criterion::default().sample_size(10);
-    targets = bench_sponge
+    targets = bench_sponge, bench_compress
 }
 criterion_main!(benches);
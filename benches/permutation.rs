@@ -0,0 +1,97 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use core::marker::PhantomData;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use dusk_plonk::prelude::*;
+use dusk_poseidon::{
+    GadgetBackend, HadesCircuitBackend, ScalarPermutation, HADES_WIDTH,
+};
+use dusk_safe::Safe;
+use ff::Field;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+const CAPACITY: usize = 11;
+
+// A circuit proving one permutation with the given backend, alternative
+// backends are compared by adding them to the benchmark targets.
+struct PermutationCircuit<B> {
+    input: [BlsScalar; HADES_WIDTH],
+    output: [BlsScalar; HADES_WIDTH],
+    backend: PhantomData<B>,
+}
+
+impl<B> Default for PermutationCircuit<B> {
+    fn default() -> Self {
+        Self {
+            input: [BlsScalar::zero(); HADES_WIDTH],
+            output: [BlsScalar::zero(); HADES_WIDTH],
+            backend: PhantomData,
+        }
+    }
+}
+
+impl<B: HadesCircuitBackend> Circuit for PermutationCircuit<B> {
+    fn circuit(&self, composer: &mut Composer) -> Result<(), Error> {
+        let mut state = self.input.map(|i| composer.append_witness(i));
+
+        B::permute(composer, &mut state);
+        state.iter().zip(self.output).for_each(|(w, o)| {
+            composer.assert_equal_constant(*w, 0, Some(o));
+        });
+
+        Ok(())
+    }
+}
+
+// Benchmark for proving and verifying one permutation with the backend, the
+// constraints of a backend are reported by `report::permutation_constraints`
+fn bench_backend<B: HadesCircuitBackend>(c: &mut Criterion) {
+    // Prepare benchmarks and initialize variables
+    let label = b"permutation benchmark";
+    let mut rng = StdRng::seed_from_u64(0xc10d);
+    let pp = PublicParameters::setup(1 << CAPACITY, &mut rng).unwrap();
+    let (prover, verifier) =
+        Compiler::compile::<PermutationCircuit<B>>(&pp, label)
+            .expect("Circuit should compile successfully");
+    let mut proof = Proof::default();
+    let input: [BlsScalar; HADES_WIDTH] =
+        core::array::from_fn(|_| BlsScalar::random(&mut rng));
+    let mut output = input;
+    ScalarPermutation::new().permute(&mut output);
+    let circuit = PermutationCircuit::<B> {
+        input,
+        output,
+        backend: PhantomData,
+    };
+
+    // Benchmark proof creation
+    c.bench_function(&format!("{} proof generation", B::NAME), |b| {
+        b.iter(|| {
+            (proof, _) = prover
+                .prove(&mut rng, black_box(&circuit))
+                .expect("Proof generation should succeed");
+        })
+    });
+
+    // Benchmark proof verification
+    c.bench_function(&format!("{} proof verification", B::NAME), |b| {
+        b.iter(|| {
+            verifier
+                .verify(black_box(&proof), &output)
+                .expect("Proof verification should succeed");
+        })
+    });
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = bench_backend::<GadgetBackend>
+}
+criterion_main!(benches);
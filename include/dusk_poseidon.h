@@ -0,0 +1,87 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ *
+ * Copyright (c) DUSK NETWORK. All rights reserved.
+ */
+
+/*
+ * The C ABI of the `hostfn` module of the dusk-poseidon crate, exported when
+ * the crate is built with the `hostfn` feature. The encryption functions are
+ * only exported when the `encryption` feature is enabled as well.
+ *
+ * Scalars are encoded in their canonical 32 byte representation and jubjub
+ * points in their compressed 32 byte representation. Every function returns
+ * one of the status codes below, with DUSK_POSEIDON_SUCCESS being the only
+ * code for which the output buffer has been written to.
+ */
+
+#ifndef DUSK_POSEIDON_H
+#define DUSK_POSEIDON_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#define DUSK_POSEIDON_SUCCESS 0
+#define DUSK_POSEIDON_INVALID_INPUT_LENGTH 1
+#define DUSK_POSEIDON_INVALID_OUTPUT_LENGTH 2
+#define DUSK_POSEIDON_INVALID_SCALAR 3
+#define DUSK_POSEIDON_INVALID_POINT 4
+#define DUSK_POSEIDON_DECRYPTION_FAILED 5
+#define DUSK_POSEIDON_INVALID_OPENING 10
+#define DUSK_POSEIDON_INVALID_POSITION 11
+
+#define DUSK_POSEIDON_SCALAR_SIZE 32
+#define DUSK_POSEIDON_MAX_INPUT_SCALARS 128
+#define DUSK_POSEIDON_MAX_MERKLE_HEIGHT 32
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+uint32_t dusk_poseidon_hash(const uint8_t *input,
+                            size_t input_len,
+                            uint8_t *output);
+
+uint32_t dusk_poseidon_hash_truncated(const uint8_t *input,
+                                      size_t input_len,
+                                      uint8_t *output);
+
+uint32_t dusk_poseidon_merkle_root(const uint8_t *leaves,
+                                   size_t leaves_len,
+                                   uint32_t height,
+                                   uint8_t *output);
+
+uint32_t dusk_poseidon_merkle_opening(const uint8_t *leaves,
+                                      size_t leaves_len,
+                                      uint32_t height,
+                                      uint64_t position,
+                                      uint8_t *output,
+                                      size_t output_len);
+
+uint32_t dusk_poseidon_merkle_verify(const uint8_t *root,
+                                     const uint8_t *leaf,
+                                     uint64_t position,
+                                     const uint8_t *opening,
+                                     size_t opening_len);
+
+uint32_t dusk_poseidon_encrypt(const uint8_t *message,
+                               size_t message_len,
+                               const uint8_t *shared_secret,
+                               const uint8_t *nonce,
+                               uint8_t *output,
+                               size_t output_len);
+
+uint32_t dusk_poseidon_decrypt(const uint8_t *cipher,
+                               size_t cipher_len,
+                               const uint8_t *shared_secret,
+                               const uint8_t *nonce,
+                               uint8_t *output,
+                               size_t output_len);
+
+#ifdef __cplusplus
+} /* extern "C" */
+#endif
+
+#endif /* DUSK_POSEIDON_H */
@@ -0,0 +1,31 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::vec::Vec;
+
+use dusk_bls12_381::BlsScalar;
+
+use crate::convert::{packed_bytes_to_scalars, PACKED_BYTES};
+use crate::hash::{SeparatedDomain, PROOF_SEPARATOR};
+use crate::Hash;
+
+/// Bind the serialized bytes of a proof into one scalar, e.g. for recursive
+/// or aggregating protocols that commit to their inner proofs.
+///
+/// The bytes are packed into scalars with
+/// [`packed_bytes_to_scalars`](crate::convert::packed_bytes_to_scalars),
+/// prefixed with their length, and hashed in a dedicated domain, so that a
+/// binding never collides with any other hash of this crate.
+pub fn bind_proof(proof_bytes: &[u8]) -> BlsScalar {
+    let mut input =
+        Vec::with_capacity(proof_bytes.len().div_ceil(PACKED_BYTES) + 1);
+    input.push(BlsScalar::from(proof_bytes.len() as u64));
+    input.extend(packed_bytes_to_scalars(proof_bytes));
+
+    Hash::new(SeparatedDomain(PROOF_SEPARATOR))
+        .update(&input)
+        .finalize()[0]
+}
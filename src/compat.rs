@@ -0,0 +1,25 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Legacy hash functions for an incremental migration to [`Hash`].
+//!
+//! The functions in this module keep the names and signatures of the
+//! `sponge` module that was removed in version 0.36.0 and produce the same
+//! outputs as that module did up to version 0.35.0. Neither the outputs of
+//! the [`Hash`] nor of the [`HashGadget`] match these legacy outputs, since
+//! the hash has been reimplemented with the SAFE framework and the number of
+//! partial rounds of the permutation has been increased to 60 in version
+//! 0.38.0.
+//!
+//! Use these functions only to verify or reproduce digests that were created
+//! with one of those earlier versions, and [`Hash`] for anything new.
+//!
+//! [`Hash`]: crate::Hash
+//! [`HashGadget`]: crate::HashGadget
+
+mod permutation;
+
+pub mod sponge;
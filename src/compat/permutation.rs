@@ -0,0 +1,100 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! The Hades permutation with the number of partial rounds used before
+//! version 0.38.0, sharing the constants and the rounds of the current
+//! permutation.
+
+use dusk_bls12_381::BlsScalar;
+#[cfg(feature = "zk")]
+use dusk_plonk::prelude::{Composer, Witness};
+
+#[cfg(feature = "zk")]
+use crate::hades::GadgetPermutation;
+use crate::hades::{Hades, ScalarPermutation, WIDTH};
+
+// The number of partial rounds of the permutation before version 0.38.0.
+const LEGACY_PARTIAL_ROUNDS: usize = 59;
+
+/// The legacy Hades permutation operating on [`BlsScalar`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct LegacyScalarPermutation(ScalarPermutation);
+
+impl LegacyScalarPermutation {
+    /// Constructs a new `LegacyScalarPermutation`.
+    pub fn new() -> Self {
+        Self(ScalarPermutation::new())
+    }
+
+    /// Applies one legacy Hades permutation to the state.
+    pub fn permute(&mut self, state: &mut [BlsScalar; WIDTH]) {
+        self.perm(state);
+    }
+}
+
+impl Hades<BlsScalar> for LegacyScalarPermutation {
+    fn partial_rounds(&self) -> usize {
+        LEGACY_PARTIAL_ROUNDS
+    }
+
+    fn add_round_constants(
+        &mut self,
+        round: usize,
+        state: &mut [BlsScalar; WIDTH],
+    ) {
+        self.0.add_round_constants(round, state);
+    }
+
+    fn quintic_s_box(&mut self, value: &mut BlsScalar) {
+        self.0.quintic_s_box(value);
+    }
+
+    fn mul_matrix(&mut self, round: usize, state: &mut [BlsScalar; WIDTH]) {
+        self.0.mul_matrix(round, state);
+    }
+}
+
+/// The legacy Hades permutation operating on [`Witness`]es.
+#[cfg(feature = "zk")]
+pub(crate) struct LegacyGadgetPermutation<'a>(GadgetPermutation<'a>);
+
+#[cfg(feature = "zk")]
+impl<'a> LegacyGadgetPermutation<'a> {
+    /// Constructs a new `LegacyGadgetPermutation` with the constraint system.
+    pub fn new(composer: &'a mut Composer) -> Self {
+        Self(GadgetPermutation::new(composer))
+    }
+
+    /// Applies one legacy Hades permutation to the state.
+    pub fn permute(&mut self, state: &mut [Witness; WIDTH]) {
+        self.perm(state);
+    }
+}
+
+#[cfg(feature = "zk")]
+impl Hades<Witness> for LegacyGadgetPermutation<'_> {
+    fn partial_rounds(&self) -> usize {
+        LEGACY_PARTIAL_ROUNDS
+    }
+
+    fn add_round_constants(
+        &mut self,
+        round: usize,
+        state: &mut [Witness; WIDTH],
+    ) {
+        self.0.add_round_constants(round, state);
+    }
+
+    fn quintic_s_box(&mut self, value: &mut Witness) {
+        self.0.quintic_s_box(value);
+    }
+
+    fn mul_matrix(&mut self, round: usize, state: &mut [Witness; WIDTH]) {
+        // the constants of the next round depend on the number of rounds
+        let rounds = self.rounds();
+        self.0.mul_matrix_in(round, rounds, state);
+    }
+}
@@ -0,0 +1,74 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! The legacy sponge hash, with the capacity element set to zero and the
+//! input padded with a one to a multiple of the rate.
+
+pub mod truncated;
+
+use alloc::vec::Vec;
+
+use dusk_bls12_381::BlsScalar;
+#[cfg(feature = "zk")]
+use dusk_plonk::prelude::{Composer, Constraint, Witness};
+
+#[cfg(feature = "zk")]
+use super::permutation::LegacyGadgetPermutation;
+use super::permutation::LegacyScalarPermutation;
+use crate::hades::{RATE, WIDTH};
+
+/// Hash an arbitrary number of scalars with the legacy sponge, with the same
+/// output as `sponge::hash` up to version 0.35.0.
+///
+/// An empty input hashes to zero.
+pub fn hash(messages: &[BlsScalar]) -> BlsScalar {
+    let mut permutation = LegacyScalarPermutation::new();
+    let mut state = [BlsScalar::zero(); WIDTH];
+
+    // the legacy sponge didn't permute at all for an empty input
+    if messages.is_empty() {
+        return state[1];
+    }
+
+    let mut padded = Vec::with_capacity(messages.len() + 1);
+    padded.extend_from_slice(messages);
+    padded.push(BlsScalar::one());
+
+    padded.chunks(RATE).for_each(|chunk| {
+        state[1..].iter_mut().zip(chunk).for_each(|(s, c)| *s += c);
+        permutation.permute(&mut state);
+    });
+
+    state[1]
+}
+
+/// Hash an arbitrary number of witnesses with the legacy sponge in the
+/// circuit, with the same output as `sponge::gadget` up to version 0.35.0.
+///
+/// An empty input hashes to the zero witness, the same as with [`hash`].
+#[cfg(feature = "zk")]
+pub fn gadget(composer: &mut Composer, messages: &[Witness]) -> Witness {
+    let mut state = [Composer::ZERO; WIDTH];
+
+    // the legacy sponge didn't permute at all for an empty input
+    if messages.is_empty() {
+        return state[1];
+    }
+
+    let mut padded = Vec::with_capacity(messages.len() + 1);
+    padded.extend_from_slice(messages);
+    padded.push(Composer::ONE);
+
+    padded.chunks(RATE).for_each(|chunk| {
+        state[1..].iter_mut().zip(chunk).for_each(|(s, c)| {
+            let constraint = Constraint::new().left(1).a(*s).right(1).b(*c);
+            *s = composer.gate_add(constraint);
+        });
+        LegacyGadgetPermutation::new(composer).permute(&mut state);
+    });
+
+    state[1]
+}
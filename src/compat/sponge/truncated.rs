@@ -0,0 +1,34 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! The legacy sponge hash, truncated to a jubjub-scalar.
+
+use dusk_bls12_381::BlsScalar;
+use dusk_jubjub::JubJubScalar;
+#[cfg(feature = "zk")]
+use dusk_plonk::prelude::{Composer, Witness};
+
+use crate::hash::truncate;
+
+/// Hash an arbitrary number of scalars with the legacy sponge and truncate
+/// the output to 250 bits, with the same output as `sponge::truncated::hash`
+/// up to version 0.35.0.
+pub fn hash(messages: &[BlsScalar]) -> JubJubScalar {
+    truncate(&super::hash(messages))
+}
+
+/// Hash an arbitrary number of witnesses with the legacy sponge in the
+/// circuit and truncate the output to 250 bits, with the same output as
+/// `sponge::truncated::gadget` up to version 0.35.0.
+///
+/// An empty input hashes to zero, the same as with [`hash`].
+#[cfg(feature = "zk")]
+pub fn gadget(composer: &mut Composer, messages: &[Witness]) -> Witness {
+    let h = super::gadget(composer, messages);
+
+    // truncate to 250 bits
+    composer.append_logic_xor::<125>(h, Composer::ZERO)
+}
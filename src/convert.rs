@@ -0,0 +1,86 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Batch conversions of plain data into the scalars absorbed by the hash
+//! functions.
+//!
+//! The conversions allocate the output once for the whole batch, and since
+//! every element is converted independently, large batches can be split among
+//! several threads by the caller.
+
+use alloc::vec::Vec;
+
+use dusk_bls12_381::BlsScalar;
+
+/// The amount of bytes of one encoded scalar.
+const SCALAR_SIZE: usize = 32;
+
+/// The amount of bytes that are packed into one scalar, any 31 bytes encode a
+/// valid scalar.
+pub const PACKED_BYTES: usize = 31;
+
+/// Convert each of the values into a scalar.
+pub fn u64s_to_scalars(values: &[u64]) -> Vec<BlsScalar> {
+    values.iter().map(|value| BlsScalar::from(*value)).collect()
+}
+
+/// Convert the canonical 32 byte encodings of scalars into the scalars they
+/// encode.
+///
+/// Returns `None` if the length of `bytes` isn't a multiple of 32, or if any
+/// of the chunks doesn't encode a valid scalar.
+pub fn bytes_to_scalars(bytes: &[u8]) -> Option<Vec<BlsScalar>> {
+    if bytes.len() % SCALAR_SIZE != 0 {
+        return None;
+    }
+
+    bytes
+        .chunks_exact(SCALAR_SIZE)
+        .map(|chunk| {
+            let mut scalar_bytes = [0u8; SCALAR_SIZE];
+            scalar_bytes.copy_from_slice(chunk);
+            Option::from(BlsScalar::from_bytes(&scalar_bytes))
+        })
+        .collect()
+}
+
+/// Convert each chunk of 64 bytes into a scalar by reducing it modulo the
+/// scalar field, which accepts any byte string of the right length.
+///
+/// Returns `None` if the length of `bytes` isn't a multiple of 64.
+pub fn wide_bytes_to_scalars(bytes: &[u8]) -> Option<Vec<BlsScalar>> {
+    if bytes.len() % (2 * SCALAR_SIZE) != 0 {
+        return None;
+    }
+
+    let scalars = bytes
+        .chunks_exact(2 * SCALAR_SIZE)
+        .map(|chunk| {
+            let mut wide_bytes = [0u8; 2 * SCALAR_SIZE];
+            wide_bytes.copy_from_slice(chunk);
+            BlsScalar::from_bytes_wide(&wide_bytes)
+        })
+        .collect();
+
+    Some(scalars)
+}
+
+/// Pack each chunk of [`PACKED_BYTES`] bytes into a scalar, zero-padding the
+/// last chunk, which accepts any byte string.
+///
+/// The packing doesn't encode the length of `bytes`, callers that absorb
+/// byte strings of different lengths need to absorb the length as well.
+pub fn packed_bytes_to_scalars(bytes: &[u8]) -> Vec<BlsScalar> {
+    bytes
+        .chunks(PACKED_BYTES)
+        .map(|chunk| {
+            let mut scalar_bytes = [0u8; SCALAR_SIZE];
+            scalar_bytes[..chunk.len()].copy_from_slice(chunk);
+            Option::<BlsScalar>::from(BlsScalar::from_bytes(&scalar_bytes))
+                .expect("31 bytes always encode a valid scalar")
+        })
+        .collect()
+}
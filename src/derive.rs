@@ -0,0 +1,30 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#[cfg(feature = "zk")]
+pub(crate) mod gadget;
+
+use dusk_bls12_381::BlsScalar;
+
+use crate::hash::{SeparatedDomain, CHILD_SEPARATOR};
+use crate::Hash;
+
+/// Derive the child with the given index from its parent in a hierarchy of
+/// keys, e.g. the accounts of a wallet.
+///
+/// The child is the hash of the `domain`, the parent and the index in a
+/// dedicated domain of this crate, where the `domain` separates independent
+/// hierarchies built from the same parent.
+/// Anyone knowing the parent can derive all of its children, so children
+/// that must only be derivable by the holder of a secret are derived from
+/// that secret, while children derived from a public value of the parent,
+/// e.g. a viewing key, can be derived by anyone holding that value.
+/// The same derivation can be proven in a circuit with
+/// [`derive_child_gadget`](crate::derive_child_gadget).
+pub fn derive_child(parent: BlsScalar, index: u64, domain: u64) -> BlsScalar {
+    let input = [BlsScalar::from(domain), parent, BlsScalar::from(index)];
+    Hash::digest(SeparatedDomain(CHILD_SEPARATOR), &input)[0]
+}
@@ -0,0 +1,33 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_plonk::prelude::{BlsScalar, Composer, Witness};
+
+use crate::gadgets::decompose;
+use crate::HashGadget;
+
+use crate::hash::{SeparatedDomain, CHILD_SEPARATOR};
+
+/// Derive the child with the index witness from the parent witness in the
+/// circuit, the same way as with [`derive_child`](crate::derive_child).
+///
+/// The index witness is asserted to fit into 64 bits, so that it is the
+/// canonical encoding of a `u64`.
+pub fn derive_child_gadget(
+    composer: &mut Composer,
+    parent: Witness,
+    index: Witness,
+    domain: u64,
+) -> Witness {
+    decompose(composer, index, 64);
+
+    let domain = composer.append_constant(BlsScalar::from(domain));
+    HashGadget::digest(
+        composer,
+        SeparatedDomain(CHILD_SEPARATOR),
+        &[domain, parent, index],
+    )[0]
+}
@@ -13,7 +13,7 @@
 //!
 //! use dusk_bls12_381::BlsScalar;
 //! use dusk_jubjub::{JubJubScalar, GENERATOR_EXTENDED, dhke};
-//! use dusk_poseidon::{decrypt, encrypt, Error};
+//! use dusk_poseidon::{decrypt, encrypt, Error, Nonce};
 //! use ff::Field;
 //! use rand::rngs::StdRng;
 //! use rand::SeedableRng;
@@ -24,19 +24,19 @@
 //! let alice_public = GENERATOR_EXTENDED * &alice_secret;
 //! let bob_secret = JubJubScalar::random(&mut rng);
 //! let bob_public = GENERATOR_EXTENDED * &bob_secret;
-//! let nonce = BlsScalar::random(&mut rng);
+//! let nonce = Nonce::random(&mut rng);
 //!
 //! // Alice encrypts a message of 3 BlsScalar using Diffie-Hellman key exchange
 //! // with Bob's public key
 //! let message = vec![BlsScalar::from(10), BlsScalar::from(20), BlsScalar::from(30)];
 //! let shared_secret = dhke(&alice_secret, &bob_public);
-//! let cipher = encrypt(&message, &shared_secret, &nonce)
+//! let cipher = encrypt(&message, shared_secret, &nonce)
 //!     .expect("Encryption should pass");
 //!
 //! // Bob decrypts the cipher using Diffie-Hellman key exchange with Alice's
 //! // public key
 //! let shared_secret = dhke(&bob_secret, &alice_public);
-//! let decrypted_message = decrypt(&cipher, &shared_secret, &nonce)
+//! let decrypted_message = decrypt(&cipher, shared_secret, &nonce)
 //!     .expect("Decryption should pass");
 //!
 //! assert_eq!(decrypted_message, message);
@@ -45,6 +45,16 @@
 #[cfg(feature = "zk")]
 pub(crate) mod gadget;
 
+mod key;
+pub use key::derive_key;
+
+mod nonce;
+pub use nonce::Nonce;
+
+mod state;
+pub use state::initial_state;
+
+use alloc::vec;
 use alloc::vec::Vec;
 
 use dusk_bls12_381::BlsScalar;
@@ -54,42 +64,77 @@ use crate::hades::ScalarPermutation;
 use crate::{Domain, Error};
 
 /// This function encrypts a given message with a shared secret point on the
-/// jubjub-curve and a bls-scalar nonce using the poseidon hash function.
+/// jubjub-curve and a nonce using the poseidon hash function.
 ///
-/// The shared secret is expected to be a valid point on the jubjub-curve.
+/// The shared secret is expected to be a valid point on the jubjub-curve. It
+/// can be passed in affine or extended coordinates, an extended point is
+/// normalized to affine coordinates once at the cost of one field inversion.
 ///
 /// The cipher-text will always yield exactly one element more than the message.
 pub fn encrypt(
     message: impl AsRef<[BlsScalar]>,
-    shared_secret: &JubJubAffine,
-    nonce: &BlsScalar,
+    shared_secret: impl Into<JubJubAffine>,
+    nonce: &Nonce,
 ) -> Result<Vec<BlsScalar>, Error> {
+    let shared_secret: JubJubAffine = shared_secret.into();
     Ok(dusk_safe::encrypt(
         ScalarPermutation::new(),
         Domain::Encryption,
         message,
         &[shared_secret.get_u(), shared_secret.get_v()],
-        nonce,
+        nonce.as_scalar(),
     )?)
 }
 
 /// This function decrypts a message from a given cipher-text with a shared
-/// secret point on the jubjub-curve and a bls-scalar nonce using the poseidon
-/// hash function.
+/// secret point on the jubjub-curve and a nonce using the poseidon hash
+/// function.
 ///
-/// The shared secret is expected to be a valid point on the jubjub-curve.
+/// The shared secret is expected to be a valid point on the jubjub-curve. It
+/// can be passed in affine or extended coordinates, an extended point is
+/// normalized to affine coordinates once at the cost of one field inversion.
 ///
 /// The cipher-text will always yield exactly one element more than the message.
 pub fn decrypt(
     cipher: impl AsRef<[BlsScalar]>,
-    shared_secret: &JubJubAffine,
-    nonce: &BlsScalar,
+    shared_secret: impl Into<JubJubAffine>,
+    nonce: &Nonce,
 ) -> Result<Vec<BlsScalar>, Error> {
+    let shared_secret: JubJubAffine = shared_secret.into();
     Ok(dusk_safe::decrypt(
         ScalarPermutation::new(),
         Domain::Encryption,
         cipher,
         &[shared_secret.get_u(), shared_secret.get_v()],
-        nonce,
+        nonce.as_scalar(),
     )?)
 }
+
+/// This function re-encrypts a cipher-text under a new shared secret and
+/// nonce, e.g. when rotating the keys of a delegation.
+///
+/// The message is decrypted with the old shared secret and nonce and
+/// encrypted again with the new ones, the message itself is never returned.
+///
+/// Both the decryption and the encryption always run, a message of zeros
+/// with the same length is encrypted if the decryption fails, so that the
+/// time taken doesn't reveal whether the old secret and nonce were valid.
+/// The error of the decryption is only returned once both have finished.
+///
+/// The new cipher-text yields the same number of elements as the old one.
+pub fn reencrypt(
+    cipher: impl AsRef<[BlsScalar]>,
+    old_shared_secret: impl Into<JubJubAffine>,
+    old_nonce: &Nonce,
+    new_shared_secret: impl Into<JubJubAffine>,
+    new_nonce: &Nonce,
+) -> Result<Vec<BlsScalar>, Error> {
+    let cipher = cipher.as_ref();
+    let zeros = vec![BlsScalar::zero(); cipher.len().saturating_sub(1)];
+
+    let decrypted = decrypt(cipher, old_shared_secret, old_nonce);
+    let message = decrypted.as_deref().unwrap_or(&zeros);
+    let encrypted = encrypt(message, new_shared_secret, new_nonce);
+
+    decrypted.and(encrypted)
+}
@@ -6,10 +6,25 @@
 
 use alloc::vec::Vec;
 
-use dusk_plonk::prelude::{Composer, Witness, WitnessPoint};
+use dusk_plonk::prelude::{
+    BlsScalar, Composer, Constraint, Witness, WitnessPoint,
+};
+use dusk_safe::Safe;
 
-use crate::hades::GadgetPermutation;
-use crate::{Domain, Error};
+use crate::hades::{GadgetPermutation, RATE, WIDTH};
+use crate::hash::{SeparatedDomain, KEY_SEPARATOR, NONCE_SEPARATOR};
+use crate::{Domain, Error, HashGadget};
+
+use super::state::{io_pattern, StateRecorder};
+
+#[cfg(feature = "debug-gadgets")]
+use crate::hades::ScalarPermutation;
+
+// Return the values of the witnesses.
+#[cfg(feature = "debug-gadgets")]
+fn values(composer: &Composer, witnesses: &[Witness]) -> Vec<BlsScalar> {
+    witnesses.iter().map(|w| composer[*w]).collect()
+}
 
 /// This function encrypts a given message with a shared secret point on the
 /// jubjub-curve and a bls-scalar nonce using the poseidon hash function.
@@ -23,13 +38,61 @@ pub fn encrypt_gadget(
     shared_secret: &WitnessPoint,
     nonce: &Witness,
 ) -> Result<Vec<Witness>, Error> {
-    Ok(dusk_safe::encrypt(
+    let cipher = dusk_safe::encrypt(
         GadgetPermutation::new(composer),
         Domain::Encryption,
-        message,
+        message.as_ref(),
         &[*shared_secret.x(), *shared_secret.y()],
         nonce,
-    )?)
+    )?;
+
+    #[cfg(feature = "debug-gadgets")]
+    {
+        let expected = dusk_safe::encrypt(
+            ScalarPermutation::new(),
+            Domain::Encryption,
+            values(composer, message.as_ref()),
+            &values(composer, &[*shared_secret.x(), *shared_secret.y()])
+                .try_into()
+                .expect("the shared secret has two coordinates"),
+            &composer[*nonce],
+        )?;
+        assert_eq!(
+            values(composer, &cipher),
+            expected,
+            "the encryption gadget diverges from the encryption"
+        );
+    }
+
+    Ok(cipher)
+}
+
+/// This function encrypts a given message with a shared secret point on the
+/// jubjub-curve and a bls-scalar nonce using the poseidon hash function, and
+/// appends the elements of the resulting cipher-text as public inputs to the
+/// circuit.
+///
+/// The public inputs are appended in the order of the returned cipher-text,
+/// which is the order in which the cipher-text needs to be passed to the
+/// verifier.
+///
+/// The shared secret is expected to be a valid point on the jubjub-curve.
+///
+/// The cipher-text will always yield exactly one element more than the message.
+pub fn encrypt_gadget_pi(
+    composer: &mut Composer,
+    message: impl AsRef<[Witness]>,
+    shared_secret: &WitnessPoint,
+    nonce: &Witness,
+) -> Result<Vec<Witness>, Error> {
+    let cipher = encrypt_gadget(composer, message, shared_secret, nonce)?;
+
+    cipher.iter().for_each(|c| {
+        let public = composer[*c];
+        composer.assert_equal_constant(*c, 0, Some(public));
+    });
+
+    Ok(cipher)
 }
 
 /// This function decrypts a message from a given cipher-text with a shared
@@ -45,11 +108,183 @@ pub fn decrypt_gadget(
     shared_secret: &WitnessPoint,
     nonce: &Witness,
 ) -> Result<Vec<Witness>, Error> {
-    Ok(dusk_safe::decrypt(
+    let message = dusk_safe::decrypt(
         GadgetPermutation::new(composer),
         Domain::Encryption,
-        cipher,
+        cipher.as_ref(),
         &[*shared_secret.x(), *shared_secret.y()],
         nonce,
-    )?)
+    )?;
+
+    // the native decryption fails for cipher-texts with an invalid tag, for
+    // which the circuit is unsatisfiable anyway
+    #[cfg(feature = "debug-gadgets")]
+    if let Ok(expected) = dusk_safe::decrypt(
+        ScalarPermutation::new(),
+        Domain::Encryption,
+        values(composer, cipher.as_ref()),
+        &values(composer, &[*shared_secret.x(), *shared_secret.y()])
+            .try_into()
+            .expect("the shared secret has two coordinates"),
+        &composer[*nonce],
+    ) {
+        assert_eq!(
+            values(composer, &message),
+            expected,
+            "the decryption gadget diverges from the decryption"
+        );
+    }
+
+    Ok(message)
+}
+
+/// This function re-encrypts a cipher-text under a new shared secret point
+/// on the jubjub-curve and a new bls-scalar nonce, proving that both
+/// cipher-texts encrypt the same message.
+///
+/// The shared secrets are expected to be valid points on the jubjub-curve.
+///
+/// The new cipher-text yields the same number of elements as the old one.
+pub fn reencrypt_gadget(
+    composer: &mut Composer,
+    cipher: impl AsRef<[Witness]>,
+    old_shared_secret: &WitnessPoint,
+    old_nonce: &Witness,
+    new_shared_secret: &WitnessPoint,
+    new_nonce: &Witness,
+) -> Result<Vec<Witness>, Error> {
+    let message =
+        decrypt_gadget(composer, cipher, old_shared_secret, old_nonce)?;
+    encrypt_gadget(composer, message, new_shared_secret, new_nonce)
+}
+
+/// Assert that the nonce witness is derived from the given domain separator
+/// and context witnesses, the same way as with
+/// [`Nonce::derive`](crate::Nonce::derive).
+pub fn assert_nonce_derived(
+    composer: &mut Composer,
+    nonce: &Witness,
+    domain: u64,
+    context: &[Witness],
+) {
+    let mut input = Vec::with_capacity(context.len() + 1);
+    input.push(composer.append_constant(BlsScalar::from(domain)));
+    input.extend_from_slice(context);
+
+    let derived =
+        HashGadget::digest(composer, SeparatedDomain(NONCE_SEPARATOR), &input);
+    composer.assert_equal(derived[0], *nonce);
+}
+
+/// Derive a secret key from the input key material witnesses and the public
+/// `info` in the circuit, the same way as with
+/// [`derive_key`](crate::derive_key).
+///
+/// The returned witness holds the derived jubjub scalar, e.g. for computing
+/// the corresponding public key with
+/// [`component_mul_generator`](Composer::component_mul_generator).
+pub fn derive_key_gadget(
+    composer: &mut Composer,
+    ikm: &[Witness],
+    info: &[BlsScalar],
+) -> Witness {
+    let mut input = Vec::with_capacity(ikm.len() + info.len() + 1);
+    input.push(composer.append_constant(BlsScalar::from(ikm.len() as u64)));
+    input.extend_from_slice(ikm);
+    input.extend(info.iter().map(|i| composer.append_constant(*i)));
+
+    HashGadget::digest_truncated(
+        composer,
+        SeparatedDomain(KEY_SEPARATOR),
+        &input,
+    )[0]
+}
+
+/// Return the state of the sponge right before its first permutation when
+/// encrypting or decrypting a message of the given length in the circuit, the
+/// same way as with [`initial_state`](crate::initial_state).
+///
+/// Returns an error when the message length is zero.
+pub fn initial_state_gadget(
+    composer: &mut Composer,
+    shared_secret: &WitnessPoint,
+    nonce: &Witness,
+    message_len: usize,
+) -> Result<[Witness; WIDTH], Error> {
+    let mut state = None;
+    let mut sponge = dusk_safe::Sponge::start(
+        StateRecorder::new(GadgetPermutation::new(composer), &mut state),
+        io_pattern(message_len),
+        Domain::Encryption.into(),
+    )?;
+    sponge.absorb(2, [*shared_secret.x(), *shared_secret.y()])?;
+    sponge.absorb(1, [*nonce])?;
+    sponge.squeeze(message_len)?;
+    drop(sponge);
+
+    Ok(state.expect("squeezing permutes the state"))
+}
+
+/// Assert that the cipher-text is well-formed under the shared secret point
+/// on the jubjub-curve and the bls-scalar nonce, i.e. that its last element
+/// is the tag [`decrypt_gadget`] checks, without constraining the message.
+///
+/// When decrypting, the sponge absorbs the message into the squeezed state,
+/// which is the same as adding the cipher-text and subtracting the squeezed
+/// key-stream. This gadget computes the absorbed state from the cipher-text
+/// directly, with one constraint per message element instead of two, and none
+/// at all for messages that fit into a single rate.
+///
+/// The shared secret is expected to be a valid point on the jubjub-curve.
+///
+/// Returns an error when the cipher-text doesn't hold at least two elements.
+pub fn verify_cipher_gadget(
+    composer: &mut Composer,
+    cipher: impl AsRef<[Witness]>,
+    shared_secret: &WitnessPoint,
+    nonce: &Witness,
+) -> Result<(), Error> {
+    let cipher = cipher.as_ref();
+    let message_len = cipher.len().saturating_sub(1);
+
+    let mut state =
+        initial_state_gadget(composer, shared_secret, nonce, message_len)?;
+
+    // squeeze the key-stream, one permutation per started rate
+    let mut key_stream = Vec::with_capacity(message_len.div_ceil(RATE));
+    for _ in 0..message_len.div_ceil(RATE) {
+        GadgetPermutation::new(composer).permute(&mut state);
+        key_stream.push(state);
+    }
+    let single_rate = key_stream.len() == 1;
+
+    // absorb the message, computed as cipher-text minus key-stream, into the
+    // state the sponge was left in after squeezing
+    for (i, c) in cipher[..message_len].iter().enumerate() {
+        let pos = i % RATE;
+        if i > 0 && pos == 0 {
+            GadgetPermutation::new(composer).permute(&mut state);
+        }
+        let pos = pos + 1;
+        state[pos] = match single_rate {
+            // the state to absorb into is the key-stream itself
+            true => *c,
+            false => {
+                let constraint = Constraint::new()
+                    .left(1)
+                    .a(state[pos])
+                    .right(1)
+                    .b(*c)
+                    .fourth(-BlsScalar::one())
+                    .d(key_stream[i / RATE][pos]);
+                composer.gate_add(constraint)
+            }
+        };
+    }
+
+    // squeeze the tag and assert it against the last element of the cipher
+    GadgetPermutation::new(composer).permute(&mut state);
+    composer.assert_equal(state[1], cipher[message_len]);
+
+    Ok(())
 }
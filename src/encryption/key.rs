@@ -0,0 +1,31 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::vec::Vec;
+
+use dusk_bls12_381::BlsScalar;
+use dusk_jubjub::JubJubScalar;
+
+use crate::hash::{SeparatedDomain, KEY_SEPARATOR};
+use crate::Hash;
+
+/// Derive a secret key from the input key material `ikm` and the public
+/// `info` binding the key to its purpose.
+///
+/// The key is the truncated hash of the length of the key material, followed
+/// by the key material and the info, so that the boundary between both can't
+/// be shifted, hashed in a dedicated domain. The derived key can be used as the
+/// secret key of a Diffie-Hellman key exchange to compute the shared secret of
+/// an encryption. The same derivation can be proven in a circuit with
+/// [`derive_key_gadget`](crate::derive_key_gadget).
+pub fn derive_key(ikm: &[BlsScalar], info: &[BlsScalar]) -> JubJubScalar {
+    let mut input = Vec::with_capacity(ikm.len() + info.len() + 1);
+    input.push(BlsScalar::from(ikm.len() as u64));
+    input.extend_from_slice(ikm);
+    input.extend_from_slice(info);
+
+    Hash::digest_truncated(SeparatedDomain(KEY_SEPARATOR), &input)[0]
+}
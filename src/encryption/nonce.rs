@@ -0,0 +1,90 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::vec::Vec;
+
+use dusk_bls12_381::BlsScalar;
+use rand_core::{CryptoRng, RngCore};
+
+use crate::hash::{SeparatedDomain, NONCE_SEPARATOR};
+use crate::Hash;
+
+/// The nonce used for the encryption and decryption of a message.
+///
+/// A nonce must never be used twice for encrypting with the same shared
+/// secret. Wrapping the nonce in its own type prevents unrelated scalars, like
+/// the coordinates of the shared secret, from being passed as nonce by
+/// accident.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Nonce(BlsScalar);
+
+impl Nonce {
+    /// The size of the byte representation of a nonce.
+    pub const SIZE: usize = 32;
+
+    /// Generate a random nonce.
+    pub fn random<R>(rng: &mut R) -> Self
+    where
+        R: RngCore + CryptoRng,
+    {
+        let mut bytes = [0u8; 64];
+        rng.fill_bytes(&mut bytes);
+        Self(BlsScalar::from_bytes_wide(&bytes))
+    }
+
+    /// Derive a nonce from a domain separator and the context of the
+    /// encryption, e.g. the scalars of the transaction it is part of.
+    ///
+    /// The nonce is the hash of the domain separator followed by the context
+    /// in a dedicated domain, so that it is unique as long as the context is
+    /// unique and never collides with any other hash of this crate. The same
+    /// derivation can be asserted in a circuit with
+    /// [`assert_nonce_derived`](crate::assert_nonce_derived).
+    pub fn derive(domain: u64, context: &[BlsScalar]) -> Self {
+        let mut input = Vec::with_capacity(context.len() + 1);
+        input.push(BlsScalar::from(domain));
+        input.extend_from_slice(context);
+        Self(Hash::digest(SeparatedDomain(NONCE_SEPARATOR), &input)[0])
+    }
+
+    /// Derive a nonce from a domain separator and a counter.
+    ///
+    /// The nonce is the hash of the domain separator and the counter, so that
+    /// incrementing the counter for every message yields unique nonces, and
+    /// protocols using different domain separators never share a nonce.
+    /// This is the same as [`Nonce::derive`] with the counter as context.
+    pub fn from_counter(domain: u64, counter: u64) -> Self {
+        Self::derive(domain, &[BlsScalar::from(counter)])
+    }
+
+    /// Return the underlying scalar of the nonce.
+    pub fn as_scalar(&self) -> &BlsScalar {
+        &self.0
+    }
+
+    /// Serialize the nonce into its canonical byte representation.
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        self.0.to_bytes()
+    }
+
+    /// Deserialize a nonce from its canonical byte representation, returns
+    /// `None` if the bytes don't encode a valid scalar.
+    pub fn from_bytes(bytes: &[u8; Self::SIZE]) -> Option<Self> {
+        Option::from(BlsScalar::from_bytes(bytes)).map(Self)
+    }
+}
+
+impl From<BlsScalar> for Nonce {
+    fn from(scalar: BlsScalar) -> Self {
+        Self(scalar)
+    }
+}
+
+impl From<Nonce> for BlsScalar {
+    fn from(nonce: Nonce) -> Self {
+        nonce.0
+    }
+}
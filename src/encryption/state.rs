@@ -0,0 +1,160 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bls12_381::BlsScalar;
+use dusk_jubjub::JubJubAffine;
+#[cfg(feature = "zk")]
+use dusk_plonk::prelude::Witness;
+use dusk_safe::{Call, Safe};
+
+use crate::hades::{ScalarPermutation, WIDTH};
+use crate::{Domain, Error, Nonce};
+
+/// The io-pattern of the encryption and decryption of a message with the
+/// given length, as defined by the SAFE framework.
+pub(crate) fn io_pattern(message_len: usize) -> [Call; 5] {
+    [
+        Call::Absorb(2),
+        Call::Absorb(1),
+        Call::Squeeze(message_len),
+        Call::Absorb(message_len),
+        Call::Squeeze(1),
+    ]
+}
+
+/// A wrapper around a permutation that records the state passed to the
+/// first permutation of the sponge instead of permuting it.
+///
+/// The sponge needs to be abandoned after the first permutation, since its
+/// state is not permuted.
+pub(crate) struct StateRecorder<'a, P, T> {
+    permutation: P,
+    state: &'a mut Option<[T; WIDTH]>,
+}
+
+impl<'a, P, T> StateRecorder<'a, P, T> {
+    /// Constructs a new `StateRecorder` wrapping the given permutation.
+    pub fn new(permutation: P, state: &'a mut Option<[T; WIDTH]>) -> Self {
+        Self { permutation, state }
+    }
+}
+
+impl<'a, P> Safe<BlsScalar, WIDTH> for StateRecorder<'a, P, BlsScalar>
+where
+    P: Safe<BlsScalar, WIDTH>,
+{
+    fn permute(&mut self, state: &mut [BlsScalar; WIDTH]) {
+        self.state.get_or_insert(*state);
+    }
+
+    fn tag(&mut self, input: &[u8]) -> BlsScalar {
+        self.permutation.tag(input)
+    }
+
+    fn add(&mut self, right: &BlsScalar, left: &BlsScalar) -> BlsScalar {
+        self.permutation.add(right, left)
+    }
+}
+
+#[cfg(feature = "zk")]
+impl<'a, P> Safe<Witness, WIDTH> for StateRecorder<'a, P, Witness>
+where
+    P: Safe<Witness, WIDTH>,
+{
+    fn permute(&mut self, state: &mut [Witness; WIDTH]) {
+        self.state.get_or_insert(*state);
+    }
+
+    fn tag(&mut self, input: &[u8]) -> Witness {
+        self.permutation.tag(input)
+    }
+
+    fn add(&mut self, right: &Witness, left: &Witness) -> Witness {
+        self.permutation.add(right, left)
+    }
+}
+
+/// Return the state of the sponge right before its first permutation when
+/// encrypting or decrypting a message of the given length.
+///
+/// The state is laid out as `[tag, secret_u, secret_v, nonce, 0]`, where the
+/// tag in the capacity element encodes [`Domain::Encryption`] and the
+/// io-pattern of the encryption, and with it the length of the message.
+/// This allows other protocols, e.g. a MAC over the cipher-text, to build on
+/// the exact state used by [`encrypt`](crate::encrypt) and
+/// [`decrypt`](crate::decrypt).
+///
+/// Returns an error when the message length is zero.
+pub fn initial_state(
+    shared_secret: impl Into<JubJubAffine>,
+    nonce: &Nonce,
+    message_len: usize,
+) -> Result<[BlsScalar; WIDTH], Error> {
+    let shared_secret: JubJubAffine = shared_secret.into();
+
+    let mut state = None;
+    let mut sponge = dusk_safe::Sponge::start(
+        StateRecorder::new(ScalarPermutation::new(), &mut state),
+        io_pattern(message_len),
+        Domain::Encryption.into(),
+    )?;
+    sponge.absorb(2, [shared_secret.get_u(), shared_secret.get_v()])?;
+    sponge.absorb(1, [*nonce.as_scalar()])?;
+    sponge.squeeze(message_len)?;
+    drop(sponge);
+
+    Ok(state.expect("squeezing permutes the state"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec::Vec;
+
+    use dusk_jubjub::GENERATOR_EXTENDED;
+
+    #[test]
+    fn initial_state_matches_encryption() {
+        let shared_secret: JubJubAffine = (GENERATOR_EXTENDED
+            * dusk_jubjub::JubJubScalar::from(42u64))
+        .into();
+        let nonce = Nonce::from(BlsScalar::from(7));
+        let message = [BlsScalar::from(1), BlsScalar::from(2)];
+
+        let initial = initial_state(shared_secret, &nonce, message.len())
+            .expect("the message length is valid");
+        let mut state = initial;
+        assert_eq!(state[1], shared_secret.get_u());
+        assert_eq!(state[2], shared_secret.get_v());
+        assert_eq!(state[3], *nonce.as_scalar());
+        assert_eq!(state[4], BlsScalar::zero());
+
+        // the tag is the tag of the io-pattern, aggregated to
+        // `[Absorb(3), Squeeze(2), Absorb(2), Squeeze(1)]`
+        let mut tag_input = Vec::new();
+        for call in [0x8000_0003u32, 2, 0x8000_0002, 1] {
+            tag_input.extend(call.to_be_bytes());
+        }
+        tag_input.extend(u64::from(Domain::Encryption).to_be_bytes());
+        assert_eq!(state[0], ScalarPermutation::new().tag(&tag_input));
+
+        // the first elements of the cipher are the permuted state added to
+        // the message
+        let cipher = crate::encrypt(message, shared_secret, &nonce)
+            .expect("encryption should pass");
+        ScalarPermutation::new().permute(&mut state);
+        assert_eq!(cipher[0], state[1] + message[0]);
+        assert_eq!(cipher[1], state[2] + message[1]);
+
+        // the tag depends on the length of the message
+        let other = initial_state(shared_secret, &nonce, 3)
+            .expect("the message length is valid");
+        assert_ne!(other[0], initial[0]);
+
+        assert!(initial_state(shared_secret, &nonce, 0).is_err());
+    }
+}
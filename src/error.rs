@@ -4,10 +4,16 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use core::fmt;
+
 use dusk_safe::Error as SafeError;
 
 /// Defines all possible error variants for SAFE
+///
+/// Every variant has a stable numeric [`code`](Error::code), e.g. for hosts
+/// mapping errors to status codes across an FFI boundary.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
 pub enum Error {
     /// A call to during the lifetime of the [`safe::Sponge`] that doesn't fit
     /// the io-pattern.
@@ -31,6 +37,39 @@ pub enum Error {
     InvalidPoint,
 }
 
+impl Error {
+    /// Return the stable numeric code of the error.
+    ///
+    /// The codes never change between releases and are never zero, which is
+    /// reserved for success. They share their numbering with the status codes
+    /// of the `hostfn` module, where the invalid point and decryption failures
+    /// are reported with the same codes.
+    pub const fn code(&self) -> u32 {
+        match self {
+            Self::InvalidPoint => 4,
+            Self::DecryptionFailed => 5,
+            Self::EncryptionFailed => 6,
+            Self::IOPatternViolation => 7,
+            Self::InvalidIOPattern => 8,
+            Self::TooFewInputElements => 9,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            Self::IOPatternViolation => "the io-pattern was violated",
+            Self::InvalidIOPattern => "the io-pattern is invalid",
+            Self::TooFewInputElements => "the input has too few elements",
+            Self::EncryptionFailed => "the encryption failed",
+            Self::DecryptionFailed => "the decryption failed",
+            Self::InvalidPoint => "the point is not on the jubjub-curve",
+        };
+        write!(f, "{description} (code {})", self.code())
+    }
+}
+
 impl From<SafeError> for Error {
     fn from(safe_error: SafeError) -> Self {
         match safe_error {
@@ -0,0 +1,55 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Introspection of the features the crate was compiled with.
+//!
+//! The functions are `const`, so that host crates can branch on the
+//! subsystems available at compile time without repeating the feature
+//! combinations of this crate in their own `cfg` attributes.
+
+/// Return `true` if the gadgets for plonk circuits are available.
+pub const fn has_zk() -> bool {
+    cfg!(feature = "zk")
+}
+
+/// Return `true` if the encryption is available, the encryption gadgets
+/// additionally need [`has_zk`].
+pub const fn has_encryption() -> bool {
+    cfg!(feature = "encryption")
+}
+
+/// Return `true` if the `hostfn` module is available.
+pub const fn has_hostfn() -> bool {
+    cfg!(feature = "hostfn")
+}
+
+/// Return `true` if the hybrid Blake2b and Poseidon hasher is available.
+pub const fn has_hybrid() -> bool {
+    cfg!(feature = "hybrid")
+}
+
+/// Return `true` if the `metrics` module is available.
+pub const fn has_metrics() -> bool {
+    cfg!(feature = "metrics")
+}
+
+/// Return `true` if the adapter to the standard library's `Hasher` trait is
+/// available.
+pub const fn has_std_hash() -> bool {
+    cfg!(feature = "std-hash")
+}
+
+/// Return `true` if the hashes of readers from the standard library are
+/// available.
+pub const fn has_std() -> bool {
+    cfg!(feature = "std")
+}
+
+/// Return `true` if the gadgets assert that their witness values match the
+/// native computations, which is meant for development only.
+pub const fn has_debug_gadgets() -> bool {
+    cfg!(feature = "debug-gadgets")
+}
@@ -0,0 +1,77 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Helper gadgets shared by the gadgets of this crate.
+
+use alloc::vec::Vec;
+
+use dusk_plonk::prelude::{BlsScalar, Composer, Constraint, Witness};
+
+use crate::MAX_RANGE_BITS;
+
+/// Decompose `value` into `bits` little-endian boolean witnesses and assert
+/// that they recompose the value.
+///
+/// This also asserts that the value is smaller than `2^bits`, since the
+/// proof creation fails when the value doesn't fit into the bits.
+///
+/// # Panics
+/// This function panics when `bits` is zero or larger than
+/// [`MAX_RANGE_BITS`](crate::MAX_RANGE_BITS).
+pub fn decompose(
+    composer: &mut Composer,
+    value: Witness,
+    bits: usize,
+) -> Vec<Witness> {
+    assert!(
+        bits > 0 && bits <= MAX_RANGE_BITS,
+        "the amount of bits needs to be between 1 and {MAX_RANGE_BITS}"
+    );
+
+    // if the value doesn't fit into the bits, the recomposition will fail
+    let bytes = composer[value].to_bytes();
+    let bits: Vec<Witness> = (0..bits)
+        .map(|i| {
+            let bit = (bytes[i / 8] >> (i % 8)) & 1;
+            let bit = composer.append_witness(BlsScalar::from(bit as u64));
+            composer.component_boolean(bit);
+            bit
+        })
+        .collect();
+
+    let recomposed = compose(composer, &bits, 1);
+    composer.assert_equal(recomposed, value);
+
+    bits
+}
+
+// Append the weighted sum `parts[0] + parts[1] * 2^shift + ...` to the circuit,
+// adding two parts per constraint.
+pub(crate) fn compose(
+    composer: &mut Composer,
+    parts: &[Witness],
+    shift: usize,
+) -> Witness {
+    let factor = BlsScalar::from(1u64 << shift);
+    let mut coefficient = BlsScalar::one();
+    let mut sum = Composer::ZERO;
+
+    for pair in parts.chunks(2) {
+        let mut constraint = Constraint::new()
+            .left(1)
+            .a(sum)
+            .right(coefficient)
+            .b(pair[0]);
+        coefficient *= factor;
+        if let Some(part) = pair.get(1) {
+            constraint = constraint.fourth(coefficient).d(*part);
+            coefficient *= factor;
+        }
+        sum = composer.gate_add(constraint);
+    }
+
+    sum
+}
@@ -33,9 +33,25 @@ const PARTIAL_ROUNDS: usize = 60;
 /// The amount of field elements that fit into the hades permutation container
 pub const WIDTH: usize = 5;
 
+/// The amount of field elements of the container that are neither absorbed
+/// into nor squeezed from the sponge
+pub const CAPACITY: usize = 1;
+
+/// The amount of field elements of the container that are absorbed into and
+/// squeezed from the sponge per permutation
+pub const RATE: usize = WIDTH - CAPACITY;
+
+#[cfg(feature = "zk")]
+pub use permutation::backend::{
+    BackendPermutation, GadgetBackend, HadesCircuitBackend,
+};
+#[cfg(feature = "zk")]
+pub use permutation::gadget::ConstantCache;
 #[cfg(feature = "zk")]
-pub(crate) use permutation::gadget::GadgetPermutation;
-pub(crate) use permutation::scalar::ScalarPermutation;
+pub use permutation::gadget::GadgetPermutation;
+pub use permutation::scalar::ScalarPermutation;
+pub(crate) use permutation::versioned::VersionedPermutation;
+pub(crate) use permutation::Hades;
 
 const fn u64_from_buffer<const N: usize>(buf: &[u8; N], i: usize) -> u64 {
     u64::from_le_bytes([
@@ -118,7 +134,7 @@ mod tests {
             .expect("Absorption of the input should work fine");
         // absorb padding of one BlsScalar::one()
         sponge
-            .absorb(1, &[BlsScalar::one()])
+            .absorb(1, [BlsScalar::one()])
             .expect("Absorption of padding should work fine");
         sponge.squeeze(1).expect("Squeezing should work fine");
         let output = sponge.finish().expect("Finish should work fine");
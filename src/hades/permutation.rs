@@ -19,9 +19,15 @@ use crate::hades::{FULL_ROUNDS, PARTIAL_ROUNDS, WIDTH};
 #[cfg(feature = "zk")]
 pub(crate) mod gadget;
 
+/// Alternative arithmetizations of the Hades permutation in a plonk-circuit.
+#[cfg(feature = "zk")]
+pub(crate) mod backend;
+
 /// Hades permutation struct operating on [`BlsScalar`].
 pub(crate) mod scalar;
 
+pub(crate) mod versioned;
+
 /// Defines the Hades252 permutation algorithm.
 ///
 /// This permutation is a 3-step process that:
@@ -32,7 +38,15 @@ pub(crate) mod scalar;
 /// This structure allows to minimize the number of non-linear ops while
 /// maintaining the security.
 pub(crate) trait Hades<T> {
-    const ROUNDS: usize = FULL_ROUNDS + PARTIAL_ROUNDS;
+    /// The number of partial rounds of the permutation.
+    fn partial_rounds(&self) -> usize {
+        PARTIAL_ROUNDS
+    }
+
+    /// The total number of rounds of the permutation.
+    fn rounds(&self) -> usize {
+        FULL_ROUNDS + self.partial_rounds()
+    }
 
     /// Add round constants to the state.
     ///
@@ -111,14 +125,15 @@ pub(crate) trait Hades<T> {
         }
 
         // Apply R_P partial rounds
-        for round in 0..PARTIAL_ROUNDS {
+        let partial_rounds = self.partial_rounds();
+        for round in 0..partial_rounds {
             self.apply_partial_round(round + FULL_ROUNDS / 2, state);
         }
 
         // Apply R_f full rounds
         for round in 0..FULL_ROUNDS / 2 {
             self.apply_full_round(
-                round + FULL_ROUNDS / 2 + PARTIAL_ROUNDS,
+                round + FULL_ROUNDS / 2 + partial_rounds,
                 state,
             );
         }
@@ -0,0 +1,76 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use core::marker::PhantomData;
+
+use dusk_plonk::prelude::*;
+use dusk_safe::Safe;
+
+use crate::hades::{GadgetPermutation, WIDTH};
+
+/// An arithmetization of the Hades permutation in a circuit.
+///
+/// Alternative arithmetizations, e.g. using custom gates once plonk supports
+/// them, implement this trait to hash with [`BackendPermutation`] and to be
+/// compared against [`GadgetBackend`] with
+/// [`permutation_constraints`](crate::report::permutation_constraints) and
+/// the permutation benchmark.
+pub trait HadesCircuitBackend {
+    /// The name of the backend, used to label its results.
+    const NAME: &'static str;
+
+    /// Apply one permutation to the state in the circuit.
+    fn permute(composer: &mut Composer, state: &mut [Witness; WIDTH]);
+}
+
+/// The backend of the [`GadgetPermutation`] of this crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GadgetBackend;
+
+impl HadesCircuitBackend for GadgetBackend {
+    const NAME: &'static str = "gadget permutation";
+
+    fn permute(composer: &mut Composer, state: &mut [Witness; WIDTH]) {
+        GadgetPermutation::new(composer).permute(state);
+    }
+}
+
+/// A permutation gadget applying the permutation of the backend `B`, which
+/// allows to hash with any [`HadesCircuitBackend`] through
+/// [`HashGadget::finalize_with`](crate::HashGadget::finalize_with).
+///
+/// The tag and the additions of the sponge are appended to the circuit the
+/// same as with [`GadgetPermutation`].
+pub struct BackendPermutation<'a, B> {
+    composer: &'a mut Composer,
+    backend: PhantomData<B>,
+}
+
+impl<'a, B: HadesCircuitBackend> BackendPermutation<'a, B> {
+    /// Constructs a new `BackendPermutation` with the circuit composer.
+    pub fn new(composer: &'a mut Composer) -> Self {
+        Self {
+            composer,
+            backend: PhantomData,
+        }
+    }
+}
+
+impl<'a, B: HadesCircuitBackend> Safe<Witness, WIDTH>
+    for BackendPermutation<'a, B>
+{
+    fn permute(&mut self, state: &mut [Witness; WIDTH]) {
+        B::permute(self.composer, state);
+    }
+
+    fn tag(&mut self, input: &[u8]) -> Witness {
+        GadgetPermutation::new(self.composer).tag(input)
+    }
+
+    fn add(&mut self, right: &Witness, left: &Witness) -> Witness {
+        GadgetPermutation::new(self.composer).add(right, left)
+    }
+}
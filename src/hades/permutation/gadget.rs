@@ -4,6 +4,8 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use alloc::vec::Vec;
+
 use dusk_bls12_381::BlsScalar;
 use dusk_plonk::prelude::*;
 use dusk_safe::Safe;
@@ -12,70 +14,102 @@ use crate::hades::{MDS_MATRIX, ROUND_CONSTANTS, WIDTH};
 
 use super::Hades;
 
-/// An implementation for the [`Hades`] permutation operating on [`Witness`]es.
-/// Requires a reference to a plonk circuit [`Composer`].
-pub(crate) struct GadgetPermutation<'a> {
-    /// A reference to the constraint system used by the gadgets
-    composer: &'a mut Composer,
+/// A cache of the constants appended to a circuit by the permutation gadget.
+///
+/// Every hash appends its domain tag as a constant to the circuit. When the
+/// cache is passed to [`finalize_cached`], a constant that has already been
+/// appended is reused instead of appending it again, which saves one witness
+/// and one constraint per repeated domain.
+///
+/// A cache must only ever be used with the [`Composer`] the cached constants
+/// have been appended to.
+///
+/// [`finalize_cached`]: crate::HashGadget::finalize_cached
+#[derive(Debug, Clone, Default)]
+pub struct ConstantCache {
+    constants: Vec<(BlsScalar, Witness)>,
 }
 
-impl<'a> GadgetPermutation<'a> {
-    /// Constructs a new `GadgetPermutation` with the constraint system.
-    pub fn new(composer: &'a mut Composer) -> Self {
-        Self { composer }
+impl ConstantCache {
+    /// Create a new empty cache.
+    pub fn new() -> Self {
+        Self::default()
     }
-}
 
-impl<'a> Safe<Witness, WIDTH> for GadgetPermutation<'a> {
-    fn permute(&mut self, state: &mut [Witness; WIDTH]) {
-        self.perm(state);
+    /// Return the number of cached constants.
+    pub fn len(&self) -> usize {
+        self.constants.len()
     }
 
-    fn tag(&mut self, input: &[u8]) -> Witness {
-        let tag = BlsScalar::hash_to_scalar(input.as_ref());
-        // append the tag as a constant
-        self.composer.append_constant(tag)
+    /// Return `true` if no constant has been cached yet.
+    pub fn is_empty(&self) -> bool {
+        self.constants.is_empty()
     }
 
-    fn add(&mut self, right: &Witness, left: &Witness) -> Witness {
-        let constraint = Constraint::new().left(1).a(*left).right(1).b(*right);
-        self.composer.gate_add(constraint)
+    /// Return the witness of the given constant, appending the constant to
+    /// the circuit if it isn't cached yet.
+    pub(crate) fn append_constant(
+        &mut self,
+        composer: &mut Composer,
+        constant: BlsScalar,
+    ) -> Witness {
+        match self.constants.iter().find(|(c, _)| *c == constant) {
+            Some((_, witness)) => *witness,
+            None => {
+                let witness = composer.append_constant(constant);
+                self.constants.push((constant, witness));
+                witness
+            }
+        }
     }
 }
 
-impl<'a> Hades<Witness> for GadgetPermutation<'a> {
-    fn add_round_constants(
-        &mut self,
-        round: usize,
-        state: &mut [Witness; WIDTH],
-    ) {
-        // To save constraints we only add the constants here in the first
-        // round. The remaining constants will be added in the matrix
-        // multiplication.
-        if round == 0 {
-            state.iter_mut().enumerate().for_each(|(i, w)| {
-                let constant = ROUND_CONSTANTS[0][i];
-                let constraint =
-                    Constraint::new().left(1).a(*w).constant(constant);
+/// An implementation for the Hades permutation operating on [`Witness`]es.
+/// Requires a reference to a plonk circuit [`Composer`].
+///
+/// This is a low-level primitive for building custom sponge gadgets with
+/// [`dusk_safe::Sponge`], the in-circuit counterpart of
+/// [`ScalarPermutation`](crate::ScalarPermutation). Prefer
+/// [`HashGadget`](crate::HashGadget) whenever possible.
+pub struct GadgetPermutation<'a> {
+    /// A reference to the constraint system used by the gadgets
+    composer: &'a mut Composer,
+    /// The cache of the constants already appended to the circuit
+    cache: Option<&'a mut ConstantCache>,
+}
 
-                *w = self.composer.gate_add(constraint);
-            });
+impl<'a> GadgetPermutation<'a> {
+    /// Constructs a new `GadgetPermutation` with the constraint system.
+    pub fn new(composer: &'a mut Composer) -> Self {
+        Self {
+            composer,
+            cache: None,
         }
     }
 
-    fn quintic_s_box(&mut self, value: &mut Witness) {
-        let constraint = Constraint::new().mult(1).a(*value).b(*value);
-        let v2 = self.composer.gate_mul(constraint);
-
-        let constraint = Constraint::new().mult(1).a(v2).b(v2);
-        let v4 = self.composer.gate_mul(constraint);
-
-        let constraint = Constraint::new().mult(1).a(v4).b(*value);
-        *value = self.composer.gate_mul(constraint);
+    /// Constructs a new `GadgetPermutation` with the constraint system that
+    /// reuses the constants of the given cache.
+    pub fn with_cache(
+        composer: &'a mut Composer,
+        cache: &'a mut ConstantCache,
+    ) -> Self {
+        Self {
+            cache: Some(cache),
+            ..Self::new(composer)
+        }
     }
 
-    /// Adds a constraint for each matrix coefficient multiplication
-    fn mul_matrix(&mut self, round: usize, state: &mut [Witness; WIDTH]) {
+    /// Multiply the MDS matrix with the state, adding the constants of the
+    /// next round of a permutation with the given total number of rounds.
+    ///
+    /// This allows permutations with a different number of rounds, like the
+    /// legacy permutation of the `compat` module, to share the constraints.
+    pub(crate) fn mul_matrix_in(
+        &mut self,
+        round: usize,
+        rounds: usize,
+        state: &mut [Witness; WIDTH],
+    ) {
         let mut result = [Composer::ZERO; WIDTH];
 
         // Implementation optimized for WIDTH = 5
@@ -101,7 +135,7 @@ impl<'a> Hades<Witness> for GadgetPermutation<'a> {
         // r[x] = q_l · w_l + q_r · w_r + q_4 · w_4 + c;
         for j in 0..WIDTH {
             // c is the next round's constant and hence zero for the last round.
-            let c = match round + 1 < Self::ROUNDS {
+            let c = match round + 1 < rounds {
                 true => ROUND_CONSTANTS[round + 1][j],
                 false => BlsScalar::zero(),
             };
@@ -132,6 +166,63 @@ impl<'a> Hades<Witness> for GadgetPermutation<'a> {
     }
 }
 
+impl<'a> Safe<Witness, WIDTH> for GadgetPermutation<'a> {
+    fn permute(&mut self, state: &mut [Witness; WIDTH]) {
+        self.perm(state);
+    }
+
+    fn tag(&mut self, input: &[u8]) -> Witness {
+        let tag = BlsScalar::hash_to_scalar(input);
+        // append the tag as a constant
+        match self.cache.as_deref_mut() {
+            Some(cache) => cache.append_constant(self.composer, tag),
+            None => self.composer.append_constant(tag),
+        }
+    }
+
+    fn add(&mut self, right: &Witness, left: &Witness) -> Witness {
+        let constraint = Constraint::new().left(1).a(*left).right(1).b(*right);
+        self.composer.gate_add(constraint)
+    }
+}
+
+impl<'a> Hades<Witness> for GadgetPermutation<'a> {
+    fn add_round_constants(
+        &mut self,
+        round: usize,
+        state: &mut [Witness; WIDTH],
+    ) {
+        // To save constraints we only add the constants here in the first
+        // round. The remaining constants will be added in the matrix
+        // multiplication.
+        if round == 0 {
+            state.iter_mut().enumerate().for_each(|(i, w)| {
+                let constant = ROUND_CONSTANTS[0][i];
+                let constraint =
+                    Constraint::new().left(1).a(*w).constant(constant);
+
+                *w = self.composer.gate_add(constraint);
+            });
+        }
+    }
+
+    fn quintic_s_box(&mut self, value: &mut Witness) {
+        let constraint = Constraint::new().mult(1).a(*value).b(*value);
+        let v2 = self.composer.gate_mul(constraint);
+
+        let constraint = Constraint::new().mult(1).a(v2).b(v2);
+        let v4 = self.composer.gate_mul(constraint);
+
+        let constraint = Constraint::new().mult(1).a(v4).b(*value);
+        *value = self.composer.gate_mul(constraint);
+    }
+
+    /// Adds a constraint for each matrix coefficient multiplication
+    fn mul_matrix(&mut self, round: usize, state: &mut [Witness; WIDTH]) {
+        self.mul_matrix_in(round, self.rounds(), state);
+    }
+}
+
 #[cfg(feature = "encryption")]
 impl dusk_safe::Encryption<Witness, WIDTH> for GadgetPermutation<'_> {
     fn subtract(&mut self, minuend: &Witness, subtrahend: &Witness) -> Witness {
@@ -10,9 +10,16 @@ use dusk_safe::Safe;
 use super::Hades;
 use crate::hades::{MDS_MATRIX, ROUND_CONSTANTS, WIDTH};
 
-/// An implementation of the [`Permutation`] for `BlsScalar` as input values.
-#[derive(Default)]
-pub(crate) struct ScalarPermutation();
+/// An implementation of the Hades permutation for `BlsScalar` as input
+/// values.
+///
+/// This is a low-level primitive for building custom sponges with
+/// [`dusk_safe::Sponge`], e.g. with an io-pattern that isn't covered by
+/// [`Hash`](crate::Hash). Prefer [`Hash`](crate::Hash) whenever possible,
+/// since the safety of a custom sponge depends on its io-pattern and
+/// domain-separator.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScalarPermutation();
 
 impl ScalarPermutation {
     /// Constructs a new `ScalarPermutation`.
@@ -27,7 +34,7 @@ impl Safe<BlsScalar, WIDTH> for ScalarPermutation {
     }
 
     fn tag(&mut self, input: &[u8]) -> BlsScalar {
-        BlsScalar::hash_to_scalar(input.as_ref())
+        BlsScalar::hash_to_scalar(input)
     }
 
     fn add(&mut self, right: &BlsScalar, left: &BlsScalar) -> BlsScalar {
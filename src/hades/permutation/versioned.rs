@@ -0,0 +1,118 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::vec::Vec;
+
+use dusk_bls12_381::BlsScalar;
+#[cfg(feature = "zk")]
+use dusk_plonk::prelude::Witness;
+use dusk_safe::Safe;
+
+use crate::hades::{FULL_ROUNDS, PARTIAL_ROUNDS, WIDTH};
+
+/// The blake2b digest (as `BlsScalar::hash_to_scalar`) of the canonical
+/// encoding of the round constants followed by the MDS matrix.
+const CONSTANTS_DIGEST: [u8; 32] = [
+    0x59, 0x7f, 0x22, 0x91, 0x7d, 0x85, 0x7f, 0x3e, 0x6e, 0xfa, 0xe7, 0xa6,
+    0xb0, 0x46, 0x1e, 0x62, 0xdb, 0x15, 0x20, 0x25, 0x08, 0x7d, 0x97, 0x6d,
+    0xdc, 0x6e, 0x3d, 0x8d, 0xc7, 0x43, 0x5d, 0x53,
+];
+
+/// The identifier of the parameters of the Hades permutation: its width, the
+/// number of full and partial rounds and the digest of its constants.
+pub(crate) const PARAMETERS_ID: [u8; 35] = {
+    let mut id = [0u8; 35];
+    id[0] = WIDTH as u8;
+    id[1] = FULL_ROUNDS as u8;
+    id[2] = PARTIAL_ROUNDS as u8;
+    let mut i = 0;
+    while i < CONSTANTS_DIGEST.len() {
+        id[i + 3] = CONSTANTS_DIGEST[i];
+        i += 1;
+    }
+    id
+};
+
+/// A wrapper around the Hades permutation that prefixes the input of the
+/// sponge tag with the [`PARAMETERS_ID`], binding the capacity element, and
+/// with it the hash output, to the parameters of the permutation.
+///
+/// The identifier only describes the Hades permutation of this crate, so no
+/// other permutation may be wrapped.
+pub(crate) struct VersionedPermutation<P>(P);
+
+impl<P> VersionedPermutation<P> {
+    /// Constructs a new `VersionedPermutation` wrapping the given permutation.
+    pub fn new(permutation: P) -> Self {
+        Self(permutation)
+    }
+}
+
+// Prefix the tag input with the parameters identifier.
+fn versioned_tag_input(input: &[u8]) -> Vec<u8> {
+    let mut versioned_input =
+        Vec::with_capacity(PARAMETERS_ID.len() + input.len());
+    versioned_input.extend_from_slice(&PARAMETERS_ID);
+    versioned_input.extend_from_slice(input);
+    versioned_input
+}
+
+impl<P> Safe<BlsScalar, WIDTH> for VersionedPermutation<P>
+where
+    P: Safe<BlsScalar, WIDTH>,
+{
+    fn permute(&mut self, state: &mut [BlsScalar; WIDTH]) {
+        self.0.permute(state);
+    }
+
+    fn tag(&mut self, input: &[u8]) -> BlsScalar {
+        self.0.tag(&versioned_tag_input(input))
+    }
+
+    fn add(&mut self, right: &BlsScalar, left: &BlsScalar) -> BlsScalar {
+        self.0.add(right, left)
+    }
+}
+
+#[cfg(feature = "zk")]
+impl<P> Safe<Witness, WIDTH> for VersionedPermutation<P>
+where
+    P: Safe<Witness, WIDTH>,
+{
+    fn permute(&mut self, state: &mut [Witness; WIDTH]) {
+        self.0.permute(state);
+    }
+
+    fn tag(&mut self, input: &[u8]) -> Witness {
+        self.0.tag(&versioned_tag_input(input))
+    }
+
+    fn add(&mut self, right: &Witness, left: &Witness) -> Witness {
+        self.0.add(right, left)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::hades::{MDS_MATRIX, ROUND_CONSTANTS};
+
+    #[test]
+    fn constants_digest() {
+        let bytes: Vec<u8> = ROUND_CONSTANTS
+            .iter()
+            .flatten()
+            .chain(MDS_MATRIX.iter().flatten())
+            .flat_map(|c| c.to_bytes())
+            .collect();
+
+        assert_eq!(
+            BlsScalar::hash_to_scalar(&bytes).to_bytes(),
+            CONSTANTS_DIGEST
+        );
+    }
+}
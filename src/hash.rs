@@ -5,16 +5,23 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use alloc::vec::Vec;
+use core::ops::Range;
 
 use dusk_bls12_381::BlsScalar;
 use dusk_jubjub::JubJubScalar;
-use dusk_safe::{Call, Sponge};
+use dusk_safe::{Call, Safe, Sponge};
 
-use crate::hades::ScalarPermutation;
+use crate::hades::{ScalarPermutation, VersionedPermutation, RATE, WIDTH};
+#[cfg(feature = "metrics")]
+use crate::metrics::{Metered, MetricsSink};
 use crate::Error;
 
+#[cfg(any(feature = "std", feature = "hostfn"))]
+pub(crate) mod duplex;
 #[cfg(feature = "zk")]
 pub(crate) mod gadget;
+#[cfg(feature = "std")]
+mod reader;
 
 /// The Domain Separation for Poseidon
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -35,14 +42,17 @@ pub enum Domain {
     Other,
 }
 
-impl From<Domain> for u64 {
+impl Domain {
+    /// Return the domain-separator of the domain, which is encoded in the tag
+    /// of the sponge together with the io-pattern.
+    ///
     /// Encryption for the domain-separator are taken from section 4.2 of the
     /// paper adapted to u64.
     /// When `Other` is selected we set the domain-separator to zero. We can do
     /// this since the io-pattern will be encoded in the tag in any case,
     /// ensuring safety from collision attacks.
-    fn from(domain: Domain) -> Self {
-        match domain {
+    pub const fn encoding(&self) -> u64 {
+        match self {
             // 2^4 - 1
             Domain::Merkle4 => 0x0000_0000_0000_000f,
             // 2^2 - 1
@@ -55,27 +65,153 @@ impl From<Domain> for u64 {
     }
 }
 
+impl From<Domain> for u64 {
+    fn from(domain: Domain) -> Self {
+        domain.encoding()
+    }
+}
+
+/// The domain-separators reserved by the hashes of this crate, with the name
+/// of the hash using them.
+///
+/// Custom domains implementing [`DomainKind`] must not return any of these
+/// encodings from [`DomainKind::tag_input`], which can be checked at compile
+/// time with [`is_reserved_encoding`].
+pub const RESERVED_ENCODINGS: [(&str, Range<u64>); 11] = [
+    ("Domain::Other", encoding_of(Domain::Other)),
+    ("Domain::Merkle2", encoding_of(Domain::Merkle2)),
+    ("Domain::Merkle4", encoding_of(Domain::Merkle4)),
+    ("Domain::Encryption", encoding_of(Domain::Encryption)),
+    // the number of metadata fields is added to the separator
+    ("leaf_hash", LEAF_SEPARATOR..2 * LEAF_SEPARATOR),
+    ("bind_proof", PROOF_SEPARATOR..PROOF_SEPARATOR + 1),
+    // the indices and the root of the set
+    ("pset", SET_SEPARATOR..SET_SEPARATOR + 2),
+    // the separators of the encryption module are reserved even when the
+    // `encryption` feature is disabled
+    ("Nonce::derive", NONCE_SEPARATOR..NONCE_SEPARATOR + 1),
+    ("derive_key", KEY_SEPARATOR..KEY_SEPARATOR + 1),
+    ("derive_child", CHILD_SEPARATOR..CHILD_SEPARATOR + 1),
+    ("range_commitment", RANGE_SEPARATOR..RANGE_SEPARATOR + 1),
+];
+
+// The separators of the hashes of this crate outside of `Domain`, the powers
+// of two from 2^33 on, distinct from the domain-separators of `Domain` and
+// from each other.
+pub(crate) const LEAF_SEPARATOR: u64 = 0x0000_0002_0000_0000;
+pub(crate) const PROOF_SEPARATOR: u64 = 0x0000_0004_0000_0000;
+pub(crate) const SET_SEPARATOR: u64 = 0x0000_0008_0000_0000;
+pub(crate) const NONCE_SEPARATOR: u64 = 0x0000_0010_0000_0000;
+pub(crate) const KEY_SEPARATOR: u64 = 0x0000_0020_0000_0000;
+pub(crate) const CHILD_SEPARATOR: u64 = 0x0000_0040_0000_0000;
+pub(crate) const RANGE_SEPARATOR: u64 = 0x0000_0080_0000_0000;
+
+// The range holding only the encoding of the given domain.
+const fn encoding_of(domain: Domain) -> Range<u64> {
+    domain.encoding()..domain.encoding() + 1
+}
+
+/// Return `true` if the domain-separator is reserved by one of the hashes of
+/// this crate, see [`RESERVED_ENCODINGS`].
+///
+/// This allows to assert at compile time that a custom domain doesn't
+/// collide with the domains of this crate:
+///
+/// ```
+/// const MY_DOMAIN: u64 = 0x7061_6972;
+/// const _: () = assert!(!dusk_poseidon::is_reserved_encoding(MY_DOMAIN));
+/// ```
+pub const fn is_reserved_encoding(encoding: u64) -> bool {
+    let mut i = 0;
+    while i < RESERVED_ENCODINGS.len() {
+        let range = &RESERVED_ENCODINGS[i].1;
+        if range.start <= encoding && encoding < range.end {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// A domain of the hash, with the rules for the input and output lengths of
+/// the hashes in that domain.
+///
+/// Implementing this trait allows to define custom domains for [`Hash`] and
+/// [`HashGadget`](crate::HashGadget) that carry their own validation.
+pub trait DomainKind {
+    /// Check that a hash with the given total input length and output length
+    /// is valid in this domain, returns [`Error::IOPatternViolation`] if it
+    /// isn't.
+    fn validate(
+        &self,
+        input_len: usize,
+        output_len: usize,
+    ) -> Result<(), Error>;
+
+    /// Return the domain-separator that is encoded in the tag of the sponge
+    /// together with the io-pattern.
+    ///
+    /// Custom domains must not return any of the [`RESERVED_ENCODINGS`].
+    fn tag_input(&self) -> u64;
+}
+
+impl DomainKind for Domain {
+    /// The merkle domains require an input of exactly their arity, and only
+    /// [`Domain::Other`] accepts an output of more than one element.
+    fn validate(
+        &self,
+        input_len: usize,
+        output_len: usize,
+    ) -> Result<(), Error> {
+        match self {
+            Domain::Merkle2 if input_len != 2 => Err(Error::IOPatternViolation),
+            Domain::Merkle4 if input_len != 4 => Err(Error::IOPatternViolation),
+            Domain::Other => Ok(()),
+            _ if output_len != 1 => Err(Error::IOPatternViolation),
+            _ => Ok(()),
+        }
+    }
+
+    fn tag_input(&self) -> u64 {
+        self.encoding()
+    }
+}
+
+/// A domain of a hash of this crate with its own domain-separator, which
+/// requires an output of one element.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct SeparatedDomain(pub u64);
+
+impl DomainKind for SeparatedDomain {
+    fn validate(
+        &self,
+        _input_len: usize,
+        output_len: usize,
+    ) -> Result<(), Error> {
+        match output_len {
+            1 => Ok(()),
+            _ => Err(Error::IOPatternViolation),
+        }
+    }
+
+    fn tag_input(&self) -> u64 {
+        self.0
+    }
+}
+
 // This function, which is called during the finalization step of the hash, will
 // always produce a valid io-pattern based on the input.
-// The function will return an error if a merkle domain is selected but the
-// given input elements don't add up to the specified arity.
-fn io_pattern<T>(
-    domain: Domain,
+// The function will return an error if the domain rejects the total input
+// and output lengths.
+fn io_pattern<D: DomainKind, T>(
+    domain: &D,
     input: &[&[T]],
     output_len: usize,
 ) -> Result<Vec<Call>, Error> {
     let mut io_pattern = Vec::new();
     // check total input length against domain
     let input_len = input.iter().fold(0, |acc, input| acc + input.len());
-    match domain {
-        Domain::Merkle2 if input_len != 2 || output_len != 1 => {
-            return Err(Error::IOPatternViolation);
-        }
-        Domain::Merkle4 if input_len != 4 || output_len != 1 => {
-            return Err(Error::IOPatternViolation);
-        }
-        _ => {}
-    }
+    domain.validate(input_len, output_len)?;
     for input in input.iter() {
         io_pattern.push(Call::Absorb(input.len()));
     }
@@ -84,39 +220,319 @@ fn io_pattern<T>(
     Ok(io_pattern)
 }
 
+// Returns the requested output length when the domain accepts it together
+// with the total length of the input, and the default output length of one
+// element otherwise.
+fn resolve_output_len<D: DomainKind, T>(
+    domain: &D,
+    input: &[&[T]],
+    output_len: usize,
+) -> usize {
+    let input_len = input.iter().fold(0, |acc, input| acc + input.len());
+    match domain.validate(input_len, output_len) {
+        Ok(()) => output_len,
+        Err(_) => 1,
+    }
+}
+
+// Returns the number of permutations the sponge will perform when absorbing
+// `input_len` elements in total and squeezing `output_len` elements
+// afterwards.
+// The sponge only permutes when the rate is exhausted by a call to absorb and
+// always permutes before the first element is squeezed, so the absorption
+// costs one permutation per full rate of input except the last one and the
+// squeezing one permutation per (started) rate of output.
+// An empty input is rejected by the sponge before it permutes at all.
+fn permutation_count(input_len: usize, output_len: usize) -> usize {
+    if input_len == 0 {
+        return 0;
+    }
+    (input_len - 1) / RATE + output_len.div_ceil(RATE)
+}
+
+// 'Cast' a bls-scalar to a jubjub-scalar by truncating the 6 highest bits.
+pub(crate) fn truncate(bls: &BlsScalar) -> JubJubScalar {
+    // bit-mask for the 250 lowest bits
+    const TRUNCATION_MASK: BlsScalar = BlsScalar::from_raw([
+        0xffff_ffff_ffff_ffff,
+        0xffff_ffff_ffff_ffff,
+        0xffff_ffff_ffff_ffff,
+        0x03ff_ffff_ffff_ffff,
+    ]);
+
+    JubJubScalar::from_raw((bls & &TRUNCATION_MASK).reduce().0)
+}
+
+/// Embed a jubjub-scalar, e.g. the output of a truncated hash, into the
+/// bls-scalar with the same canonical value.
+///
+/// The embedding of a jubjub-scalar witness in a circuit is asserted with
+/// [`embed_jubjub_gadget`](crate::embed_jubjub_gadget).
+pub fn embed_jubjub(scalar: &JubJubScalar) -> BlsScalar {
+    BlsScalar::from(*scalar)
+}
+
+// Return the merkle domain of the given arity.
+fn merkle_domain(arity: usize) -> Domain {
+    match arity {
+        2 => Domain::Merkle2,
+        4 => Domain::Merkle4,
+        _ => panic!("there is no merkle domain for an arity of {arity}"),
+    }
+}
+
+/// Hash one level of a merkle tree, or of any other tree-like structure, with
+/// the merkle domain of the level's arity `A`.
+///
+/// Any empty slots of the level need to be filled with the zero element.
+///
+/// # Panics
+/// This function panics when the arity is neither 2 nor 4.
+pub fn level_hash<const A: usize>(level: &[BlsScalar; A]) -> BlsScalar {
+    Hash::digest(merkle_domain(A), level)[0]
+}
+
+// Hash one level of a tree, reporting the workload to the sink.
+#[cfg(feature = "metrics")]
+pub(crate) fn metered_level_hash<const A: usize>(
+    level: &[BlsScalar; A],
+    sink: &dyn MetricsSink,
+) -> BlsScalar {
+    sink.record_level();
+    let mut hash = Hash::new(merkle_domain(A));
+    hash.update(level);
+    hash.finalize_with_sink(ScalarPermutation::new(), sink)[0]
+}
+
+// The tag of a hash in the `Merkle2` domain, i.e. of the io-pattern
+// `[Absorb(2), Squeeze(1)]` with the domain-separator 3. It is pinned here so
+// that `compress` can skip the hashing of the io-pattern.
+pub(crate) const MERKLE2_TAG: BlsScalar = BlsScalar::from_raw([
+    0x5052_e51d_d58d_4c41,
+    0x4e68_146e_f09a_1453,
+    0x7c32_3ce5_5e81_1a0e,
+    0x19cf_f317_c82d_d38a,
+]);
+
+/// Compress two scalar into one with a single permutation.
+///
+/// This is the cheapest two-to-one primitive of the crate, it returns the same
+/// digest as `Hash::digest(Domain::Merkle2, &[a, b])[0]` but runs the
+/// permutation directly on the state, without computing the tag of the
+/// io-pattern or allocating.
+///
+/// The in-circuit counterpart is [`compress_gadget`](crate::compress_gadget).
+pub fn compress(a: &BlsScalar, b: &BlsScalar) -> BlsScalar {
+    let mut state = [MERKLE2_TAG, *a, *b, BlsScalar::zero(), BlsScalar::zero()];
+    ScalarPermutation::new().permute(&mut state);
+    state[1]
+}
+
+// Compress two scalars into one, reporting the workload to the sink.
+#[cfg(feature = "metrics")]
+pub(crate) fn metered_compress(
+    a: &BlsScalar,
+    b: &BlsScalar,
+    sink: &dyn MetricsSink,
+) -> BlsScalar {
+    sink.record_hash();
+    let mut state = [MERKLE2_TAG, *a, *b, BlsScalar::zero(), BlsScalar::zero()];
+    Metered::new(ScalarPermutation::new(), sink).permute(&mut state);
+    state[1]
+}
+
+// Compute the hash output of the input in the domain with the sponge
+// framework.
+pub(crate) fn sponge<D, P>(
+    domain: &D,
+    input: &[&[BlsScalar]],
+    output_len: usize,
+    permutation: P,
+) -> Vec<BlsScalar>
+where
+    D: DomainKind,
+    P: Safe<BlsScalar, WIDTH>,
+{
+    // Generate the hash using the sponge framework:
+    // initialize the sponge
+    let mut sponge = Sponge::start(
+        permutation,
+        io_pattern(domain, input, output_len)
+            .expect("io-pattern should be valid"),
+        domain.tag_input(),
+    )
+    .expect("at this point the io-pattern is valid");
+
+    // absorb the input
+    for input in input.iter() {
+        sponge
+            .absorb(input.len(), input)
+            .expect("at this point the io-pattern is valid");
+    }
+
+    // squeeze output_len elements
+    sponge
+        .squeeze(output_len)
+        .expect("at this point the io-pattern is valid");
+
+    // return the result
+    sponge
+        .finish()
+        .expect("at this point the io-pattern is valid")
+}
+
+// Wraps the scalar permutation and computes the tag only once, the tag of
+// hashes with the same domain and io-pattern is the same for every hash.
+struct CachedTag<'a> {
+    permutation: ScalarPermutation,
+    tag: &'a mut Option<BlsScalar>,
+}
+
+impl<'a> Safe<BlsScalar, WIDTH> for CachedTag<'a> {
+    fn permute(&mut self, state: &mut [BlsScalar; WIDTH]) {
+        self.permutation.permute(state);
+    }
+
+    fn tag(&mut self, input: &[u8]) -> BlsScalar {
+        *self.tag.get_or_insert_with(|| self.permutation.tag(input))
+    }
+
+    fn add(&mut self, right: &BlsScalar, left: &BlsScalar) -> BlsScalar {
+        self.permutation.add(right, left)
+    }
+}
+
+/// Hash each of the equally sized rows into one scalar, with the same output
+/// as calling [`Hash::digest`] on every row.
+///
+/// Since all rows share the same io-pattern, the tag of the sponge is
+/// computed only once for all of them. The rows are independent of each
+/// other, so that large batches can also be split among several threads by
+/// the caller.
+///
+/// # Panics
+/// This function panics when the io-pattern can not be created with the
+/// given domain and rows, e.g. using [`Domain::Merkle4`] with rows of
+/// anything other than 4 Scalar.
+pub fn hash_rows<D, const N: usize>(
+    domain: D,
+    rows: &[[BlsScalar; N]],
+) -> Vec<BlsScalar>
+where
+    D: DomainKind + Copy,
+{
+    let mut tag = None;
+
+    rows.iter()
+        .map(|row| {
+            let permutation = CachedTag {
+                permutation: ScalarPermutation::new(),
+                tag: &mut tag,
+            };
+            let mut hash = Hash::new(domain);
+            hash.update(row);
+            hash.finalize_sponge(permutation)[0]
+        })
+        .collect()
+}
+
+// Hash each of the rows into one scalar, reporting the workload to the sink.
+#[cfg(feature = "metrics")]
+pub(crate) fn metered_hash_rows<D, const N: usize>(
+    domain: D,
+    rows: &[[BlsScalar; N]],
+    sink: &dyn MetricsSink,
+) -> Vec<BlsScalar>
+where
+    D: DomainKind + Copy,
+{
+    let mut tag = None;
+
+    rows.iter()
+        .map(|row| {
+            // every row but the first reuses the tag cached by the first
+            if tag.is_some() {
+                sink.record_tag_cache_hit();
+            }
+            let permutation = CachedTag {
+                permutation: ScalarPermutation::new(),
+                tag: &mut tag,
+            };
+            let mut hash = Hash::new(domain);
+            hash.update(row);
+            hash.finalize_with_sink(permutation, sink)[0]
+        })
+        .collect()
+}
+
 /// Hash any given input into one or several scalar using the Hades
 /// permutation strategy. The Hash can absorb multiple chunks of input but will
 /// only call `squeeze` once at the finalization of the hash.
 /// The output length is set to 1 element per default, but this can be
 /// overridden with [`Hash::output_len`].
-pub struct Hash<'a> {
-    domain: Domain,
+pub struct Hash<'a, D = Domain> {
+    domain: D,
     input: Vec<&'a [BlsScalar]>,
     output_len: usize,
+    versioned: bool,
 }
 
-impl<'a> Hash<'a> {
+impl<'a, D: DomainKind> Hash<'a, D> {
     /// Create a new hash.
-    pub fn new(domain: Domain) -> Self {
+    pub fn new(domain: D) -> Self {
         Self {
             domain,
             input: Vec::new(),
             output_len: 1,
+            versioned: false,
         }
     }
 
-    /// Override the length of the hash output (default value is 1) when using
-    /// the hash for anything other than hashing a merkle tree or
-    /// encryption.
-    pub fn output_len(&mut self, output_len: usize) {
-        if self.domain == Domain::Other && output_len > 0 {
+    /// Create a new versioned hash.
+    ///
+    /// A versioned hash binds its output to the parameters of the Hades
+    /// permutation (the width, the number of rounds and the digest of the
+    /// round constants and MDS matrix) by absorbing an identifier of those
+    /// parameters into the capacity element of the sponge.
+    /// This allows to tell apart long-lived digests created with different
+    /// parameters. The output of a versioned hash differs from the output of
+    /// a hash created with [`Hash::new`].
+    pub fn versioned(domain: D) -> Self {
+        Self {
+            versioned: true,
+            ..Self::new(domain)
+        }
+    }
+
+    /// Override the length of the hash output (default value is 1).
+    ///
+    /// The value is ignored when finalizing the hash unless the domain accepts
+    /// it for the total input, e.g. of the domains of [`Domain`] only
+    /// [`Domain::Other`] accepts an output of more than one element.
+    ///
+    /// Returns a mutable reference to the hash to allow chaining calls.
+    pub fn output_len(&mut self, output_len: usize) -> &mut Self {
+        if output_len > 0 {
             self.output_len = output_len;
         }
+        self
     }
 
     /// Update the hash input.
-    pub fn update(&mut self, input: &'a [BlsScalar]) {
+    ///
+    /// Returns a mutable reference to the hash to allow chaining calls.
+    pub fn update(&mut self, input: &'a [BlsScalar]) -> &mut Self {
         self.input.push(input);
+        self
+    }
+
+    /// Return the number of Hades permutations that will be performed when
+    /// finalizing the hash with the current input and output length.
+    ///
+    /// This allows to meter the cost of a hash before actually computing it.
+    pub fn permutation_count(&self) -> usize {
+        let input_len = self.input.iter().fold(0, |acc, i| acc + i.len());
+        permutation_count(input_len, self.resolved_output_len())
     }
 
     /// Finalize the hash.
@@ -126,32 +542,83 @@ impl<'a> Hash<'a> {
     /// given domain and input, e.g. using [`Domain::Merkle4`] with an input
     /// anything other than 4 Scalar.
     pub fn finalize(&self) -> Vec<BlsScalar> {
-        // Generate the hash using the sponge framework:
-        // initialize the sponge
-        let mut sponge = Sponge::start(
-            ScalarPermutation::new(),
-            io_pattern(self.domain, &self.input, self.output_len)
-                .expect("io-pattern should be valid"),
-            self.domain.into(),
-        )
-        .expect("at this point the io-pattern is valid");
+        self.finalize_sponge(ScalarPermutation::new())
+    }
 
-        // absorb the input
-        for input in self.input.iter() {
-            sponge
-                .absorb(input.len(), input)
-                .expect("at this point the io-pattern is valid");
-        }
+    /// Finalize the hash using the given permutation instead of the Hades
+    /// permutation.
+    ///
+    /// This allows to plug alternative permutations over a state of
+    /// [`HADES_WIDTH`](crate::HADES_WIDTH) elements into the sponge, e.g. for
+    /// benchmarking. Any such permutation needs to implement the
+    /// [`Safe`] trait of the SAFE framework.
+    ///
+    /// # Panics
+    /// This function panics when the hash is versioned, since the identifier
+    /// of the Hades parameters doesn't identify any other permutation, and
+    /// when the io-pattern can not be created with the given domain and
+    /// input, e.g. using [`Domain::Merkle4`] with an input anything other
+    /// than 4 Scalar.
+    pub fn finalize_with<P>(&self, permutation: P) -> Vec<BlsScalar>
+    where
+        P: Safe<BlsScalar, WIDTH>,
+    {
+        assert!(
+            !self.versioned,
+            "a versioned hash can only be finalized with the Hades permutation"
+        );
+        self.finalize_sponge(permutation)
+    }
 
-        // squeeze output_len elements
-        sponge
-            .squeeze(self.output_len)
-            .expect("at this point the io-pattern is valid");
+    /// Finalize the hash, reporting the hash and its permutations to the
+    /// sink.
+    ///
+    /// # Panics
+    /// This function panics when the io-pattern can not be created with the
+    /// given domain and input, e.g. using [`Domain::Merkle4`] with an input
+    /// anything other than 4 Scalar.
+    #[cfg(feature = "metrics")]
+    pub fn finalize_metered(&self, sink: &dyn MetricsSink) -> Vec<BlsScalar> {
+        self.finalize_with_sink(ScalarPermutation::new(), sink)
+    }
 
-        // return the result
-        sponge
-            .finish()
-            .expect("at this point the io-pattern is valid")
+    // Finalize the hash with the permutation, reporting the workload to the
+    // sink.
+    #[cfg(feature = "metrics")]
+    fn finalize_with_sink<P>(
+        &self,
+        permutation: P,
+        sink: &dyn MetricsSink,
+    ) -> Vec<BlsScalar>
+    where
+        P: Safe<BlsScalar, WIDTH>,
+    {
+        sink.record_hash();
+        self.finalize_sponge(Metered::new(permutation, sink))
+    }
+
+    // Finalize the hash with the permutation. The permutation is expected to
+    // be the Hades permutation when the hash is versioned.
+    fn finalize_sponge<P>(&self, permutation: P) -> Vec<BlsScalar>
+    where
+        P: Safe<BlsScalar, WIDTH>,
+    {
+        let output_len = self.resolved_output_len();
+        if self.versioned {
+            sponge(
+                &self.domain,
+                &self.input,
+                output_len,
+                VersionedPermutation::new(permutation),
+            )
+        } else {
+            sponge(&self.domain, &self.input, output_len, permutation)
+        }
+    }
+
+    // The output length used when finalizing the hash.
+    fn resolved_output_len(&self) -> usize {
+        resolve_output_len(&self.domain, &self.input, self.output_len)
     }
 
     /// Finalize the hash and output the result as a `JubJubScalar` by
@@ -162,24 +629,10 @@ impl<'a> Hash<'a> {
     /// given domain and input, e.g. using [`Domain::Merkle4`] with an input
     /// anything other than 4 Scalar.
     pub fn finalize_truncated(&self) -> Vec<JubJubScalar> {
-        // bit-mask to 'cast' a bls-scalar result to a jubjub-scalar by
-        // truncating the 6 highest bits
-        const TRUNCATION_MASK: BlsScalar = BlsScalar::from_raw([
-            0xffff_ffff_ffff_ffff,
-            0xffff_ffff_ffff_ffff,
-            0xffff_ffff_ffff_ffff,
-            0x03ff_ffff_ffff_ffff,
-        ]);
-
         // finalize the hash as bls-scalar
         let bls_output = self.finalize();
 
-        bls_output
-            .iter()
-            .map(|bls| {
-                JubJubScalar::from_raw((bls & &TRUNCATION_MASK).reduce().0)
-            })
-            .collect()
+        bls_output.iter().map(truncate).collect()
     }
 
     /// Digest an input and calculate the hash immediately
@@ -188,7 +641,7 @@ impl<'a> Hash<'a> {
     /// This function panics when the io-pattern can not be created with the
     /// given domain and input, e.g. using [`Domain::Merkle4`] with an input
     /// anything other than 4 Scalar.
-    pub fn digest(domain: Domain, input: &'a [BlsScalar]) -> Vec<BlsScalar> {
+    pub fn digest(domain: D, input: &'a [BlsScalar]) -> Vec<BlsScalar> {
         let mut hash = Self::new(domain);
         hash.update(input);
         hash.finalize()
@@ -201,7 +654,7 @@ impl<'a> Hash<'a> {
     /// given domain and input, e.g. using [`Domain::Merkle4`] with an input
     /// anything other than 4 Scalar.
     pub fn digest_truncated(
-        domain: Domain,
+        domain: D,
         input: &'a [BlsScalar],
     ) -> Vec<JubJubScalar> {
         let mut hash = Self::new(domain);
@@ -209,3 +662,173 @@ impl<'a> Hash<'a> {
         hash.finalize_truncated()
     }
 }
+
+impl<'a> Hash<'a, Domain> {
+    /// Digest one level of a binary merkle tree with [`Domain::Merkle2`].
+    ///
+    /// Unlike [`Hash::digest`] with [`Domain::Merkle2`], an input of the
+    /// wrong length is rejected at compile time.
+    pub fn digest_merkle2(level: &'a [BlsScalar; 2]) -> BlsScalar {
+        Self::digest(Domain::Merkle2, level)[0]
+    }
+
+    /// Digest one level of a quaternary merkle tree with [`Domain::Merkle4`].
+    ///
+    /// Unlike [`Hash::digest`] with [`Domain::Merkle4`], an input of the
+    /// wrong length is rejected at compile time.
+    pub fn digest_merkle4(level: &'a [BlsScalar; 4]) -> BlsScalar {
+        Self::digest(Domain::Merkle4, level)[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+    use std::format;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    use super::*;
+
+    use core::cell::Cell;
+
+    // Wraps the scalar permutation and counts the calls to permute.
+    struct CountingPermutation<'a> {
+        count: &'a Cell<usize>,
+    }
+
+    impl Safe<BlsScalar, WIDTH> for CountingPermutation<'_> {
+        fn permute(&mut self, state: &mut [BlsScalar; WIDTH]) {
+            self.count.set(self.count.get() + 1);
+            ScalarPermutation::new().permute(state);
+        }
+
+        fn tag(&mut self, input: &[u8]) -> BlsScalar {
+            ScalarPermutation::new().tag(input)
+        }
+
+        fn add(&mut self, right: &BlsScalar, left: &BlsScalar) -> BlsScalar {
+            right + left
+        }
+    }
+
+    #[test]
+    fn permutation_count_matches_sponge() {
+        let input = [BlsScalar::one(); 20];
+
+        for input_len in 0..input.len() {
+            for output_len in 1..10 {
+                let mut hash = Hash::new(Domain::Other);
+                hash.update(&input[..input_len]);
+                hash.output_len(output_len);
+
+                let count = Cell::new(0);
+                let output = catch_unwind(AssertUnwindSafe(|| {
+                    hash.finalize_with(CountingPermutation { count: &count })
+                }));
+
+                match input_len {
+                    0 => assert!(output.is_err(), "empty input is rejected"),
+                    _ => assert_eq!(output.unwrap(), hash.finalize()),
+                }
+                assert_eq!(
+                    hash.permutation_count(),
+                    count.get(),
+                    "input: {input_len}, output: {output_len}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn domain_validation() {
+        assert!(Domain::Merkle2.validate(2, 1).is_ok());
+        assert!(Domain::Merkle2.validate(3, 1).is_err());
+        assert!(Domain::Merkle2.validate(2, 2).is_err());
+
+        assert!(Domain::Merkle4.validate(4, 1).is_ok());
+        assert!(Domain::Merkle4.validate(2, 1).is_err());
+        assert!(Domain::Merkle4.validate(4, 2).is_err());
+
+        assert!(Domain::Encryption.validate(5, 1).is_ok());
+        assert!(Domain::Encryption.validate(5, 2).is_err());
+
+        assert!(Domain::Other.validate(0, 1).is_ok());
+        assert!(Domain::Other.validate(7, 3).is_ok());
+    }
+
+    #[test]
+    fn output_len_validated_by_domain() {
+        let input = [BlsScalar::one(); 4];
+
+        let mut hash = Hash::new(Domain::Other);
+        hash.update(&input).output_len(2);
+        assert_eq!(hash.finalize().len(), 2);
+
+        let mut hash = Hash::new(Domain::Merkle4);
+        hash.update(&input).output_len(2);
+        assert_eq!(hash.finalize().len(), 1);
+
+        let mut hash = Hash::new(Domain::Merkle4);
+        hash.output_len(2).update(&input);
+        assert_eq!(hash.finalize(), Hash::digest(Domain::Merkle4, &input));
+        // the encryption domain keeps the single element output it had before
+        // the domains carried their own validation
+        let mut hash = Hash::new(Domain::Encryption);
+        hash.update(&input).output_len(3);
+        assert_eq!(hash.finalize(), Hash::digest(Domain::Encryption, &input));
+    }
+
+    #[test]
+    fn versioned() {
+        let input = [BlsScalar::one(), BlsScalar::from(2)];
+
+        let mut hash = Hash::versioned(Domain::Other);
+        hash.update(&input);
+        let versioned = hash.finalize();
+
+        assert_ne!(versioned, Hash::digest(Domain::Other, &input));
+
+        // the versioned digest only changes with the permutation parameters
+        assert_eq!(
+            "0x572636138a911497f5bf37f2566911be9d43258ce30b32c61c7cf1fb12e0b6c5",
+            format!("{:?}", versioned[0])
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn versioned_with_other_permutation() {
+        let input = [BlsScalar::one(), BlsScalar::from(2)];
+
+        let mut hash = Hash::versioned(Domain::Other);
+        hash.update(&input);
+        hash.finalize_with(ScalarPermutation::new());
+    }
+
+    #[test]
+    fn compress_matches_merkle2() {
+        let a = BlsScalar::from(42);
+        let b = BlsScalar::from(1337);
+
+        assert_eq!(compress(&a, &b), Hash::digest(Domain::Merkle2, &[a, b])[0]);
+    }
+
+    #[test]
+    fn chained_calls() {
+        let input = [BlsScalar::one(); 7];
+
+        let mut hash = Hash::new(Domain::Other);
+        hash.update(&input[..3]);
+        hash.update(&input[3..]);
+        hash.output_len(2);
+
+        assert_eq!(
+            Hash::new(Domain::Other)
+                .update(&input[..3])
+                .update(&input[3..])
+                .output_len(2)
+                .finalize(),
+            hash.finalize()
+        );
+    }
+}
@@ -0,0 +1,159 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bls12_381::BlsScalar;
+use dusk_safe::{Call, Safe};
+
+use crate::hades::{ScalarPermutation, CAPACITY, RATE, WIDTH};
+
+// The largest number of aggregated calls of an io-pattern that can be encoded
+// into a tag by `tag`, which covers the hashes and the encryption.
+const MAX_TAG_CALLS: usize = 4;
+
+// The mask marking an encoded call as a call to absorb.
+const ABSORB_MASK: u32 = 0x8000_0000;
+
+/// Compute the tag of the io-pattern with the domain-separator, the same as
+/// [`dusk_safe::Sponge::start`] but without allocating.
+///
+/// Consecutive calls of the same kind are aggregated, the same as in the
+/// sponge, and the io-pattern is expected to be valid.
+///
+/// # Panics
+/// This function panics when the io-pattern aggregates to more than four
+/// calls.
+pub(crate) fn tag(io_pattern: &[Call], domain_sep: u64) -> BlsScalar {
+    let mut encoded = [0u32; MAX_TAG_CALLS];
+    let mut len = 0;
+    for call in io_pattern {
+        let prev_absorbs = len > 0 && encoded[len - 1] & ABSORB_MASK != 0;
+        match call {
+            Call::Absorb(n) if prev_absorbs => encoded[len - 1] += *n as u32,
+            Call::Squeeze(n) if len > 0 && !prev_absorbs => {
+                encoded[len - 1] += *n as u32
+            }
+            Call::Absorb(n) => {
+                encoded[len] = ABSORB_MASK + *n as u32;
+                len += 1;
+            }
+            Call::Squeeze(n) => {
+                encoded[len] = *n as u32;
+                len += 1;
+            }
+        }
+    }
+
+    let mut input = [0u8; 4 * MAX_TAG_CALLS + 8];
+    for (chunk, call) in input.chunks_exact_mut(4).zip(&encoded[..len]) {
+        chunk.copy_from_slice(&call.to_be_bytes());
+    }
+    input[4 * len..4 * len + 8].copy_from_slice(&domain_sep.to_be_bytes());
+
+    ScalarPermutation::new().tag(&input[..4 * len + 8])
+}
+
+/// The state of a sponge absorbing and squeezing with the Hades permutation,
+/// the same as [`dusk_safe::Sponge`] but without an io-pattern and without
+/// collecting the output, so that it never allocates.
+///
+/// The io-pattern of the calls is only encoded in the tag the state is
+/// initialized with, following it is up to the caller.
+pub(crate) struct Duplex {
+    state: [BlsScalar; WIDTH],
+    pos_absorb: usize,
+    pos_squeeze: usize,
+}
+
+impl Duplex {
+    pub fn new(tag: BlsScalar) -> Self {
+        let mut state = [BlsScalar::zero(); WIDTH];
+        state[0] = tag;
+        Self {
+            state,
+            pos_absorb: 0,
+            pos_squeeze: 0,
+        }
+    }
+
+    pub fn absorb(&mut self, input: &[BlsScalar]) {
+        for element in input {
+            if self.pos_absorb == RATE {
+                ScalarPermutation::new().permute(&mut self.state);
+                self.pos_absorb = 0;
+            }
+            self.state[self.pos_absorb + CAPACITY] += element;
+            self.pos_absorb += 1;
+        }
+        // force a permutation at the next call to squeeze
+        self.pos_squeeze = RATE;
+    }
+
+    pub fn squeeze(&mut self, output: &mut [BlsScalar]) {
+        for element in output {
+            if self.pos_squeeze == RATE {
+                ScalarPermutation::new().permute(&mut self.state);
+                self.pos_squeeze = 0;
+                self.pos_absorb = 0;
+            }
+            *element = self.state[self.pos_squeeze + CAPACITY];
+            self.pos_squeeze += 1;
+        }
+    }
+}
+
+impl Drop for Duplex {
+    fn drop(&mut self) {
+        self.state = [BlsScalar::zero(); WIDTH];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use dusk_safe::Sponge;
+
+    use crate::Domain;
+
+    #[test]
+    fn matches_sponge() {
+        let input: [BlsScalar; 7] = core::array::from_fn(|i| (i as u64).into());
+        let io_pattern = [
+            Call::Absorb(2),
+            Call::Absorb(5),
+            Call::Squeeze(3),
+            Call::Absorb(1),
+            Call::Squeeze(6),
+        ];
+        let domain_sep = Domain::Encryption.encoding();
+
+        let mut sponge =
+            Sponge::start(ScalarPermutation::new(), io_pattern, domain_sep)
+                .expect("io-pattern is valid");
+        sponge
+            .absorb(2, &input[..2])
+            .expect("absorbing should pass");
+        sponge
+            .absorb(5, &input[2..])
+            .expect("absorbing should pass");
+        sponge.squeeze(3).expect("squeezing should pass");
+        sponge
+            .absorb(1, &input[..1])
+            .expect("absorbing should pass");
+        sponge.squeeze(6).expect("squeezing should pass");
+        let expected = sponge.finish().expect("io-pattern is followed");
+
+        let mut output = [BlsScalar::zero(); 9];
+        let mut duplex = Duplex::new(tag(&io_pattern, domain_sep));
+        duplex.absorb(&input[..2]);
+        duplex.absorb(&input[2..]);
+        duplex.squeeze(&mut output[..3]);
+        duplex.absorb(&input[..1]);
+        duplex.squeeze(&mut output[3..]);
+
+        assert_eq!(&output[..], &expected[..]);
+    }
+}
@@ -6,43 +6,89 @@
 
 use alloc::vec::Vec;
 
-use dusk_plonk::prelude::{Composer, Witness};
-use dusk_safe::Sponge;
+use dusk_jubjub::JubJubScalar;
+use dusk_plonk::prelude::{BlsScalar, Composer, Constraint, Witness};
+use dusk_safe::{Safe, Sponge};
 
-use crate::hades::GadgetPermutation;
-use crate::Domain;
+use crate::gadgets::decompose;
+use crate::hades::{
+    ConstantCache, GadgetPermutation, VersionedPermutation, WIDTH,
+};
+use crate::{Domain, DomainKind};
 
-use super::io_pattern;
+use super::{
+    io_pattern, merkle_domain, permutation_count, resolve_output_len,
+    MERKLE2_TAG,
+};
 
 /// Hash struct.
-pub struct HashGadget<'a> {
-    domain: Domain,
+pub struct HashGadget<'a, D = Domain> {
+    domain: D,
     input: Vec<&'a [Witness]>,
     output_len: usize,
+    versioned: bool,
 }
 
-impl<'a> HashGadget<'a> {
+impl<'a, D: DomainKind> HashGadget<'a, D> {
     /// Create a new hash.
-    pub fn new(domain: Domain) -> Self {
+    pub fn new(domain: D) -> Self {
         Self {
             domain,
             input: Vec::new(),
             output_len: 1,
+            versioned: false,
         }
     }
 
-    /// Override the length of the hash output (default value is 1) when using
-    /// the hash for anything other than hashing a merkle tree or
-    /// encryption.
-    pub fn output_len(&mut self, output_len: usize) {
-        if self.domain == Domain::Other && output_len > 0 {
+    /// Create a new versioned hash.
+    ///
+    /// A versioned hash binds its output to the parameters of the Hades
+    /// permutation (the width, the number of rounds and the digest of the
+    /// round constants and MDS matrix) by absorbing an identifier of those
+    /// parameters into the capacity element of the sponge.
+    /// This allows to tell apart long-lived digests created with different
+    /// parameters. The output of a versioned hash differs from the output of
+    /// a hash created with [`HashGadget::new`].
+    pub fn versioned(domain: D) -> Self {
+        Self {
+            versioned: true,
+            ..Self::new(domain)
+        }
+    }
+
+    /// Override the length of the hash output (default value is 1).
+    ///
+    /// The value is ignored when finalizing the hash unless the domain accepts
+    /// it for the total input, e.g. of the domains of [`Domain`] only
+    /// [`Domain::Other`] accepts an output of more than one element.
+    ///
+    /// Returns a mutable reference to the hash to allow chaining calls.
+    pub fn output_len(&mut self, output_len: usize) -> &mut Self {
+        if output_len > 0 {
             self.output_len = output_len;
         }
+        self
     }
 
     /// Update the hash input.
-    pub fn update(&mut self, input: &'a [Witness]) {
+    ///
+    /// Returns a mutable reference to the hash to allow chaining calls.
+    pub fn update(&mut self, input: &'a [Witness]) -> &mut Self {
         self.input.push(input);
+        self
+    }
+
+    /// Return the number of Hades permutations that will be appended to the
+    /// circuit when finalizing the hash with the current input and output
+    /// length.
+    pub fn permutation_count(&self) -> usize {
+        let input_len = self.input.iter().fold(0, |acc, i| acc + i.len());
+        permutation_count(input_len, self.resolved_output_len())
+    }
+
+    // The output length used when finalizing the hash.
+    fn resolved_output_len(&self) -> usize {
+        resolve_output_len(&self.domain, &self.input, self.output_len)
     }
 
     /// Finalize the hash.
@@ -52,13 +98,133 @@ impl<'a> HashGadget<'a> {
     /// given domain and input, e.g. using [`Domain::Merkle4`] with an input
     /// anything other than 4 Scalar.
     pub fn finalize(&self, composer: &mut Composer) -> Vec<Witness> {
+        let output = self.finalize_hades(GadgetPermutation::new(composer));
+        #[cfg(feature = "debug-gadgets")]
+        self.assert_native(composer, &output);
+        output
+    }
+
+    /// Finalize the hash and constrain its (first) output to be equal to the
+    /// `expected` constant, e.g. for proving the knowledge of a preimage of a
+    /// digest fixed in the circuit.
+    ///
+    /// Use [`assert_digest_eq`] when the expected digest is a public input.
+    ///
+    /// # Panics
+    /// This function panics when the io-pattern can not be created with the
+    /// given domain and input, e.g. using [`Domain::Merkle4`] with an input
+    /// anything other than 4 Scalar.
+    pub fn finalize_expect(
+        &self,
+        composer: &mut Composer,
+        expected: BlsScalar,
+    ) {
+        let output = self.finalize(composer);
+        composer.assert_equal_constant(output[0], expected, None);
+    }
+
+    /// Finalize the hash, reusing the constants of the cache that have
+    /// already been appended to the circuit by previous hashes.
+    ///
+    /// The output is the same as with [`HashGadget::finalize`], but circuits
+    /// computing many hashes save a witness and a constraint for each domain
+    /// tag that is already cached.
+    ///
+    /// # Panics
+    /// This function panics when the io-pattern can not be created with the
+    /// given domain and input, e.g. using [`Domain::Merkle4`] with an input
+    /// anything other than 4 Scalar.
+    pub fn finalize_cached(
+        &self,
+        composer: &mut Composer,
+        cache: &mut ConstantCache,
+    ) -> Vec<Witness> {
+        let output =
+            self.finalize_hades(GadgetPermutation::with_cache(composer, cache));
+        #[cfg(feature = "debug-gadgets")]
+        self.assert_native(composer, &output);
+        output
+    }
+
+    /// Finalize the hash using the given permutation gadget instead of the
+    /// Hades permutation gadget.
+    ///
+    /// The permutation gadget is expected to hold the reference to the
+    /// circuit [`Composer`] and needs to implement the [`Safe`] trait of the
+    /// SAFE framework.
+    ///
+    /// # Panics
+    /// This function panics when the hash is versioned, since the identifier
+    /// of the Hades parameters doesn't identify any other permutation, and
+    /// when the io-pattern can not be created with the given domain and
+    /// input, e.g. using [`Domain::Merkle4`] with an input anything other
+    /// than 4 Scalar.
+    pub fn finalize_with<P>(&self, permutation: P) -> Vec<Witness>
+    where
+        P: Safe<Witness, WIDTH>,
+    {
+        assert!(
+            !self.versioned,
+            "a versioned hash can only be finalized with the Hades permutation"
+        );
+        self.sponge(permutation)
+    }
+
+    // Finalize the hash with the Hades permutation gadget, binding the output
+    // to the identifier of its parameters when the hash is versioned.
+    fn finalize_hades(&self, permutation: GadgetPermutation) -> Vec<Witness> {
+        if self.versioned {
+            self.sponge(VersionedPermutation::new(permutation))
+        } else {
+            self.sponge(permutation)
+        }
+    }
+
+    // Assert that the values of the output witnesses are the native hash of
+    // the values of the input witnesses.
+    #[cfg(feature = "debug-gadgets")]
+    fn assert_native(&self, composer: &Composer, output: &[Witness]) {
+        use crate::hades::ScalarPermutation;
+
+        let input: Vec<Vec<BlsScalar>> = self
+            .input
+            .iter()
+            .map(|input| input.iter().map(|w| composer[*w]).collect())
+            .collect();
+        let input: Vec<&[BlsScalar]> =
+            input.iter().map(Vec::as_slice).collect();
+
+        let permutation = ScalarPermutation::new();
+        let output_len = self.resolved_output_len();
+        let expected = match self.versioned {
+            true => super::sponge(
+                &self.domain,
+                &input,
+                output_len,
+                VersionedPermutation::new(permutation),
+            ),
+            false => {
+                super::sponge(&self.domain, &input, output_len, permutation)
+            }
+        };
+        let output: Vec<BlsScalar> =
+            output.iter().map(|w| composer[*w]).collect();
+        assert_eq!(output, expected, "the hash gadget diverges from the hash");
+    }
+
+    // Compute the hash output with the sponge framework.
+    fn sponge<P>(&self, permutation: P) -> Vec<Witness>
+    where
+        P: Safe<Witness, WIDTH>,
+    {
         // Generate the hash using the sponge framework:
         // initialize the sponge
+        let output_len = self.resolved_output_len();
         let mut sponge = Sponge::start(
-            GadgetPermutation::new(composer),
-            io_pattern(self.domain, &self.input, self.output_len)
+            permutation,
+            io_pattern(&self.domain, &self.input, output_len)
                 .expect("io-pattern should be valid"),
-            self.domain.into(),
+            self.domain.tag_input(),
         )
         .expect("at this point the io-pattern is valid");
 
@@ -71,7 +237,7 @@ impl<'a> HashGadget<'a> {
 
         // squeeze output_len elements
         sponge
-            .squeeze(self.output_len)
+            .squeeze(output_len)
             .expect("at this point the io-pattern is valid");
 
         // return the result
@@ -93,7 +259,7 @@ impl<'a> HashGadget<'a> {
         // truncate the bls witnesses to 250 bits
         bls_output
             .iter()
-            .map(|bls| composer.append_logic_xor::<125>(*bls, Composer::ZERO))
+            .map(|bls| truncate(composer, *bls).0)
             .collect()
     }
 
@@ -105,7 +271,7 @@ impl<'a> HashGadget<'a> {
     /// anything other than 4 Scalar.
     pub fn digest(
         composer: &mut Composer,
-        domain: Domain,
+        domain: D,
         input: &'a [Witness],
     ) -> Vec<Witness> {
         let mut hash = Self::new(domain);
@@ -121,11 +287,293 @@ impl<'a> HashGadget<'a> {
     /// anything other than 4 Scalar.
     pub fn digest_truncated(
         composer: &mut Composer,
-        domain: Domain,
+        domain: D,
         input: &'a [Witness],
     ) -> Vec<Witness> {
         let mut hash = Self::new(domain);
         hash.update(input);
         hash.finalize_truncated(composer)
     }
+
+    /// Digest the records given as columns, where the `k`-th element of each
+    /// column is a field of the `k`-th record, and return the hash of each
+    /// record.
+    ///
+    /// The output is the same as calling [`HashGadget::digest`] on every
+    /// record, but all hashes share the domain tag appended to the circuit,
+    /// saving one witness and one constraint for every record but the first.
+    ///
+    /// # Panics
+    /// This function panics when the columns are not of equal length, or when
+    /// the io-pattern can not be created with the given domain and the number
+    /// of columns, e.g. using [`Domain::Merkle4`] with anything other than 4
+    /// columns.
+    pub fn digest_columns(
+        composer: &mut Composer,
+        domain: D,
+        columns: &[&[Witness]],
+    ) -> Vec<Witness>
+    where
+        D: Copy,
+    {
+        let records = columns.first().map_or(0, |column| column.len());
+        assert!(
+            columns.iter().all(|column| column.len() == records),
+            "the columns need to be of equal length"
+        );
+
+        let mut cache = ConstantCache::new();
+        (0..records)
+            .map(|k| {
+                let record: Vec<Witness> =
+                    columns.iter().map(|column| column[k]).collect();
+                let mut hash = HashGadget::new(domain);
+                hash.update(&record);
+                hash.finalize_cached(composer, &mut cache)[0]
+            })
+            .collect()
+    }
+}
+
+impl<'a> HashGadget<'a, Domain> {
+    /// Digest one level of a binary merkle tree in the circuit with
+    /// [`Domain::Merkle2`].
+    ///
+    /// Unlike [`HashGadget::digest`] with [`Domain::Merkle2`], an input of
+    /// the wrong length is rejected at compile time instead of panicking
+    /// while building the circuit.
+    pub fn digest_merkle2(
+        composer: &mut Composer,
+        level: &'a [Witness; 2],
+    ) -> Witness {
+        HashGadget::digest(composer, Domain::Merkle2, level)[0]
+    }
+
+    /// Digest one level of a quaternary merkle tree in the circuit with
+    /// [`Domain::Merkle4`].
+    ///
+    /// Unlike [`HashGadget::digest`] with [`Domain::Merkle4`], an input of
+    /// the wrong length is rejected at compile time instead of panicking
+    /// while building the circuit.
+    pub fn digest_merkle4(
+        composer: &mut Composer,
+        level: &'a [Witness; 4],
+    ) -> Witness {
+        HashGadget::digest(composer, Domain::Merkle4, level)[0]
+    }
+}
+
+/// Hash one level of a merkle tree, or of any other tree-like structure, in
+/// the circuit with the merkle domain of the level's arity `A`.
+///
+/// Any empty slots of the level need to be filled with the zero element.
+///
+/// # Panics
+/// This function panics when the arity is neither 2 nor 4.
+pub fn level_hash_gadget<const A: usize>(
+    composer: &mut Composer,
+    level: &[Witness; A],
+) -> Witness {
+    HashGadget::digest(composer, merkle_domain(A), level)[0]
+}
+
+/// Compress two witnesses into one with a single permutation.
+///
+/// This is the in-circuit counterpart of [`compress`](crate::compress) and
+/// constrains the same digest as [`HashGadget::digest`] with
+/// [`Domain::Merkle2`], appending the pinned tag as a constant instead of
+/// computing it from the io-pattern.
+pub fn compress_gadget(
+    composer: &mut Composer,
+    a: Witness,
+    b: Witness,
+) -> Witness {
+    let tag = composer.append_constant(MERKLE2_TAG);
+    let mut state = [tag, a, b, Composer::ZERO, Composer::ZERO];
+    GadgetPermutation::new(composer).permute(&mut state);
+    state[1]
+}
+
+/// Assert that the value of the witness is a canonical jubjub-scalar, i.e.
+/// smaller than the order of the jubjub scalar field, and return it for use
+/// as a bls-scalar witness.
+///
+/// This is the in-circuit counterpart of [`embed_jubjub`](crate::embed_jubjub)
+/// and costs two decompositions of 252 bits: one of the value and one of the
+/// difference between the largest jubjub-scalar and the value, which only
+/// fits into 252 bits when the value doesn't exceed the largest
+/// jubjub-scalar.
+pub fn embed_jubjub_gadget(composer: &mut Composer, value: Witness) -> Witness {
+    const JUBJUB_BITS: usize = 252;
+
+    decompose(composer, value, JUBJUB_BITS);
+
+    let max = BlsScalar::from(-JubJubScalar::one());
+    let constraint = Constraint::new()
+        .left(-BlsScalar::one())
+        .a(value)
+        .constant(max);
+    let difference = composer.gate_add(constraint);
+    decompose(composer, difference, JUBJUB_BITS);
+
+    value
+}
+
+// Truncate the bls-scalar witness to its 250 lowest bits, the in-circuit
+// counterpart of `truncate`.
+//
+// The value is split into its 250 lowest and its 5 highest bits, both
+// decomposed with `decompose`. The split is asserted to be canonical, i.e. the
+// bits to encode an integer smaller than the modulus, so that the truncation
+// can't be taken from `value + p` instead of `value`.
+fn truncate(
+    composer: &mut Composer,
+    value: Witness,
+) -> (Witness, Vec<Witness>) {
+    const TRUNCATED_BITS: usize = 250;
+    const HIGH_BITS: usize = 5;
+
+    // the 250 lowest and the 5 highest bits of the largest bls-scalar
+    let mut max = (-BlsScalar::one()).to_bytes();
+    let max_high = BlsScalar::from((max[31] >> 2) as u64);
+    max[31] &= 0x03;
+    let max_low = BlsScalar::from_bytes(&max).expect("the bytes are canonical");
+
+    let mut bytes = composer[value].to_bytes();
+    let high =
+        composer.append_witness(BlsScalar::from((bytes[31] >> 2) as u64));
+    bytes[31] &= 0x03;
+    let low = composer.append_witness(
+        BlsScalar::from_bytes(&bytes).expect("the bytes are canonical"),
+    );
+
+    // the decompositions recompose into the split
+    let low_bits = decompose(composer, low, TRUNCATED_BITS);
+    decompose(composer, high, HIGH_BITS);
+
+    // value = low + 2^250 * high
+    let constraint = Constraint::new()
+        .left(1)
+        .a(low)
+        .right(BlsScalar::pow_of_2(TRUNCATED_BITS as u64))
+        .b(high)
+        .fourth(-BlsScalar::one())
+        .d(value);
+    composer.append_gate(constraint);
+
+    // the high bits don't exceed the ones of the largest bls-scalar
+    let constraint = Constraint::new()
+        .left(-BlsScalar::one())
+        .a(high)
+        .constant(max_high);
+    let high_difference = composer.gate_add(constraint);
+    let high_difference_bits = decompose(composer, high_difference, HIGH_BITS);
+
+    // when they are equal, the low bits don't exceed the ones of the largest
+    // bls-scalar either
+    let mut is_max_high = composer.append_constant(BlsScalar::one());
+    for bit in high_difference_bits {
+        let constraint = Constraint::new()
+            .mult(-BlsScalar::one())
+            .left(1)
+            .a(is_max_high)
+            .b(bit);
+        is_max_high = composer.gate_mul(constraint);
+    }
+    let constraint = Constraint::new()
+        .left(-BlsScalar::one())
+        .a(low)
+        .constant(max_low);
+    let low_difference = composer.gate_add(constraint);
+    let low_difference =
+        composer.component_select_zero(is_max_high, low_difference);
+    decompose(composer, low_difference, TRUNCATED_BITS);
+
+    (low, low_bits)
+}
+
+// Digest an input and return the little-endian bits of the first output
+// truncated to 250 bits, reusing the decomposition of the truncation.
+pub(crate) fn digest_truncated_bits<D: DomainKind>(
+    composer: &mut Composer,
+    domain: D,
+    input: &[Witness],
+) -> Vec<Witness> {
+    let hash = HashGadget::digest(composer, domain, input);
+    truncate(composer, hash[0]).1
+}
+
+/// Digest an input in the circuit and constrain the resulting hash to be equal
+/// to the `expected` digest, which is appended as public input.
+///
+/// This is a shorthand for the common pattern of computing a hash with
+/// [`HashGadget::digest`] and asserting its (first) output against a public
+/// input.
+///
+/// # Panics
+/// This function panics when the io-pattern can not be created with the
+/// given domain and input, e.g. using [`Domain::Merkle4`] with an input
+/// anything other than 4 Scalar.
+pub fn assert_digest_eq<D: DomainKind>(
+    composer: &mut Composer,
+    domain: D,
+    input: &[Witness],
+    expected: BlsScalar,
+) {
+    let output = HashGadget::digest(composer, domain, input);
+    composer.assert_equal_constant(output[0], 0, Some(expected));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use dusk_plonk::prelude::{Circuit, Compiler, Error, PublicParameters};
+    use ff::Field;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[derive(Debug, Default)]
+    struct TruncateCircuit {
+        value: BlsScalar,
+    }
+
+    impl Circuit for TruncateCircuit {
+        fn circuit(&self, composer: &mut Composer) -> Result<(), Error> {
+            let value = composer.append_witness(self.value);
+            let (truncated, _) = truncate(composer, value);
+
+            let expected = super::super::truncate(&self.value);
+            composer.assert_equal_constant(truncated, 0, Some(expected.into()));
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn truncate_matches_native() -> Result<(), Error> {
+        let mut rng = StdRng::seed_from_u64(0xbeef);
+        let pp = PublicParameters::setup(1 << 11, &mut rng)?;
+        let (prover, verifier) =
+            Compiler::compile::<TruncateCircuit>(&pp, b"truncate")?;
+
+        let two_250 = BlsScalar::pow_of_2(250);
+        let values = [
+            BlsScalar::zero(),
+            two_250 - BlsScalar::one(),
+            two_250,
+            // the smallest value with the highest bits of the modulus
+            BlsScalar::from(28) * two_250,
+            -BlsScalar::one(),
+            BlsScalar::random(&mut rng),
+            BlsScalar::random(&mut rng),
+        ];
+        for value in values {
+            let circuit = TruncateCircuit { value };
+            let (proof, public_inputs) = prover.prove(&mut rng, &circuit)?;
+            verifier.verify(&proof, &public_inputs)?;
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,84 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+extern crate std;
+
+use alloc::vec;
+use alloc::vec::Vec;
+use std::io::{self, Read, Seek, SeekFrom};
+
+use dusk_bls12_381::BlsScalar;
+use dusk_safe::Call;
+
+use super::duplex::{self, Duplex};
+use crate::convert::{packed_bytes_to_scalars, PACKED_BYTES};
+use crate::{DomainKind, Hash};
+
+// The amount of scalars absorbed per call to the sponge, which bounds the
+// memory used when digesting a reader.
+const CHUNK_SCALARS: usize = 1024;
+
+impl<'a, D: DomainKind> Hash<'a, D> {
+    /// Digest the bytes of the reader, from its current position to its end,
+    /// while holding at most a fixed-size chunk of them in memory.
+    ///
+    /// The memory used doesn't depend on the length of the input: the chunks
+    /// are absorbed into the state of the sponge as they are read, and the
+    /// io-pattern encoded in the tag only holds the total length.
+    ///
+    /// The bytes are packed into scalars with
+    /// [`packed_bytes_to_scalars`](crate::convert::packed_bytes_to_scalars)
+    /// and prefixed with their length, i.e. the digest is the same as
+    /// [`Hash::digest`] of the length followed by all packed bytes.
+    ///
+    /// The reader needs to be seekable since the sponge encodes the length of
+    /// the whole input in its tag before absorbing anything.
+    ///
+    /// Returns an error when reading from or seeking in the reader fails.
+    ///
+    /// # Panics
+    /// This function panics when the domain rejects the length of the packed
+    /// input, e.g. a merkle domain for an input of a different length than
+    /// its arity.
+    pub fn digest_reader<R: Read + Seek>(
+        domain: D,
+        mut reader: R,
+    ) -> io::Result<Vec<BlsScalar>> {
+        let start = reader.stream_position()?;
+        let len = reader.seek(SeekFrom::End(0))? - start;
+        reader.seek(SeekFrom::Start(start))?;
+
+        let len_bytes = usize::try_from(len).map_err(|_| {
+            io::Error::new(io::ErrorKind::InvalidInput, "input too large")
+        })?;
+        let input_len = len_bytes.div_ceil(PACKED_BYTES) + 1;
+        domain
+            .validate(input_len, 1)
+            .expect("io-pattern should be valid");
+
+        // the io-pattern encoded in the tag aggregates the absorption of all
+        // chunks into one call
+        let mut duplex = Duplex::new(duplex::tag(
+            &[Call::Absorb(input_len), Call::Squeeze(1)],
+            domain.tag_input(),
+        ));
+        duplex.absorb(&[BlsScalar::from(len)]);
+
+        let chunk_bytes = CHUNK_SCALARS * PACKED_BYTES;
+        let mut buffer = vec![0u8; chunk_bytes.min(len_bytes)];
+        let mut remaining = len_bytes;
+        while remaining > 0 {
+            let bytes = remaining.min(chunk_bytes);
+            reader.read_exact(&mut buffer[..bytes])?;
+            duplex.absorb(&packed_bytes_to_scalars(&buffer[..bytes]));
+            remaining -= bytes;
+        }
+
+        let mut output = vec![BlsScalar::zero()];
+        duplex.squeeze(&mut output);
+        Ok(output)
+    }
+}
@@ -0,0 +1,62 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::vec::Vec;
+use core::hash::{BuildHasher, Hasher};
+
+use dusk_bls12_381::BlsScalar;
+
+use crate::convert::{packed_bytes_to_scalars, PACKED_BYTES};
+use crate::{Domain, Hash};
+
+/// An adapter of the Poseidon hash to the [`Hasher`] trait of the standard
+/// library, e.g. for hash maps in testing tools.
+///
+/// The written bytes are packed into scalars, prefixed with their length, and
+/// hashed with [`Domain::Other`]. The result of [`Hasher::finish`] is the
+/// lowest 64 bits of that hash.
+///
+/// **The 64-bit output is not collision resistant and must not be used for
+/// any security decisions.** Use [`Hash`] for anything beyond bucketing.
+#[derive(Debug, Clone, Default)]
+pub struct PoseidonHasher {
+    bytes: Vec<u8>,
+}
+
+impl PoseidonHasher {
+    /// Create a new hasher.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Hasher for PoseidonHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let mut input =
+            Vec::with_capacity(self.bytes.len().div_ceil(PACKED_BYTES) + 1);
+        input.push(BlsScalar::from(self.bytes.len() as u64));
+        input.extend(packed_bytes_to_scalars(&self.bytes));
+
+        let hash = Hash::digest(Domain::Other, &input)[0];
+        hash.reduce().0[0]
+    }
+}
+
+/// A [`BuildHasher`] creating [`PoseidonHasher`]s.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PoseidonBuildHasher;
+
+impl BuildHasher for PoseidonBuildHasher {
+    type Hasher = PoseidonHasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        PoseidonHasher::new()
+    }
+}
@@ -0,0 +1,603 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Flat functions for the integration of the crate as native host functions
+//! of a VM.
+//!
+//! All functions operate on byte-slices only: scalars are encoded in their
+//! canonical 32 byte representation and jubjub points in their compressed 32
+//! byte representation. The results are written into buffers provided by the
+//! caller and every function returns one of the status codes defined in this
+//! module, with [`SUCCESS`] being the only code for which the output buffer
+//! has been written to.
+//!
+//! Since the functions use neither generics nor any types of this crate, they
+//! are also suited as the layer exposed to foreign language bindings.
+//!
+//! # C ABI
+//!
+//! Every function is also exported as an `extern "C"` function taking
+//! pointers and lengths instead of slices, e.g. [`dusk_poseidon_hash`] for
+//! [`poseidon_hash`]. The exported symbols are prefixed with `dusk_` so that
+//! they don't clash with other Poseidon libraries linked into the same host.
+//! Their prototypes, together with the status codes and sizes, are declared
+//! in the `include/dusk_poseidon.h` header.
+//!
+//! # Allocation
+//!
+//! The hashes don't allocate: their inputs are decoded one scalar at a time
+//! and the sponge runs without recording its io-pattern or output. The
+//! encryption and the merkle functions run the implementations of the crate,
+//! with the inputs decoded into fixed-size buffers on the stack or as they
+//! are consumed, and only allocate as much as those implementations do.
+//! The inputs of the hashes and of the encryption are bounded to
+//! [`MAX_INPUT_SCALARS`] scalars each.
+
+use core::slice;
+
+use dusk_bls12_381::BlsScalar;
+use dusk_safe::Call;
+
+#[cfg(feature = "encryption")]
+use dusk_jubjub::JubJubAffine;
+
+use crate::hash::duplex::{self, Duplex};
+use crate::hash::truncate;
+use crate::merkle::{opening, root_from_leaves, verify_opening};
+#[cfg(feature = "encryption")]
+use crate::Nonce;
+use crate::{Domain, Error, MAX_MERKLE_ARITY};
+
+/// The call was successful and the output has been written.
+pub const SUCCESS: u32 = 0;
+
+/// The length of an input buffer doesn't match the expected length.
+pub const INVALID_INPUT_LENGTH: u32 = 1;
+
+/// The length of the output buffer doesn't match the expected length.
+pub const INVALID_OUTPUT_LENGTH: u32 = 2;
+
+/// An input doesn't encode a valid scalar.
+pub const INVALID_SCALAR: u32 = 3;
+
+/// An input doesn't encode a valid point on the jubjub-curve.
+pub const INVALID_POINT: u32 = Error::InvalidPoint.code();
+
+/// The cipher-text couldn't be decrypted with the given secret and nonce.
+pub const DECRYPTION_FAILED: u32 = Error::DecryptionFailed.code();
+
+/// The opening doesn't lead from the leaf at the position to the root.
+pub const INVALID_OPENING: u32 = 10;
+
+/// There is no leaf at the position to open.
+pub const INVALID_POSITION: u32 = 11;
+
+/// The amount of bytes of one encoded scalar.
+pub const SCALAR_SIZE: usize = 32;
+
+/// The largest number of scalars encoded in the input of a hash, or in the
+/// message or cipher-text of the encryption.
+///
+/// This is the size of the buffers the inputs are decoded into on the stack.
+pub const MAX_INPUT_SCALARS: usize = 128;
+
+/// The largest height of the trees whose root can be computed with
+/// [`poseidon_merkle_root`], and whose openings can be computed and verified.
+pub const MAX_MERKLE_HEIGHT: u32 = 32;
+
+// Check that the byte-length encodes a non-zero number of scalars that
+// doesn't exceed the maximum, and return that number.
+fn scalar_count(bytes: &[u8]) -> Result<usize, u32> {
+    match bytes.len() % SCALAR_SIZE == 0 {
+        true => match bytes.len() / SCALAR_SIZE {
+            len @ 1..=MAX_INPUT_SCALARS => Ok(len),
+            _ => Err(INVALID_INPUT_LENGTH),
+        },
+        false => Err(INVALID_INPUT_LENGTH),
+    }
+}
+
+// Decode a 32 byte chunk into the scalar it encodes.
+fn decode(chunk: &[u8]) -> Result<BlsScalar, u32> {
+    let mut bytes = [0u8; SCALAR_SIZE];
+    bytes.copy_from_slice(chunk);
+    Option::from(BlsScalar::from_bytes(&bytes)).ok_or(INVALID_SCALAR)
+}
+
+// Decode the scalars encoded in `bytes` into the buffer and return the
+// number of decoded scalars.
+#[cfg(feature = "encryption")]
+fn decode_into(
+    bytes: &[u8],
+    buffer: &mut [BlsScalar; MAX_INPUT_SCALARS],
+) -> Result<usize, u32> {
+    let len = scalar_count(bytes)?;
+    for (scalar, chunk) in buffer.iter_mut().zip(bytes.chunks(SCALAR_SIZE)) {
+        *scalar = decode(chunk)?;
+    }
+    Ok(len)
+}
+
+// Encode the scalars into the output, which is of the same length as the
+// encoding.
+#[cfg(feature = "encryption")]
+fn encode_into(scalars: &[BlsScalar], output: &mut [u8]) {
+    output
+        .chunks_exact_mut(SCALAR_SIZE)
+        .zip(scalars)
+        .for_each(|(chunk, scalar)| chunk.copy_from_slice(&scalar.to_bytes()));
+}
+
+// Hash the scalars encoded in `input` in `Domain::Other`.
+fn hash(input: &[u8]) -> Result<BlsScalar, u32> {
+    let len = scalar_count(input)?;
+
+    let mut duplex = Duplex::new(duplex::tag(
+        &[Call::Absorb(len), Call::Squeeze(1)],
+        Domain::Other.encoding(),
+    ));
+    for chunk in input.chunks(SCALAR_SIZE) {
+        duplex.absorb(&[decode(chunk)?]);
+    }
+
+    let mut output = [BlsScalar::zero()];
+    duplex.squeeze(&mut output);
+    Ok(output[0])
+}
+
+/// Hash the scalars encoded in `input` with [`Domain::Other`] and write the
+/// encoded hash into `output`.
+///
+/// The length of `input` needs to be a non-zero multiple of 32 encoding at
+/// most [`MAX_INPUT_SCALARS`] scalars.
+pub fn poseidon_hash(input: &[u8], output: &mut [u8; SCALAR_SIZE]) -> u32 {
+    match hash(input) {
+        Ok(hash) => {
+            output.copy_from_slice(&hash.to_bytes());
+            SUCCESS
+        }
+        Err(code) => code,
+    }
+}
+
+/// The C ABI of [`poseidon_hash`], with the input being the `input_len`
+/// bytes at `input` and the output the 32 bytes at `output`.
+///
+/// # Safety
+/// `input` needs to be valid for reads of `input_len` bytes and `output`
+/// valid for writes of 32 bytes, both non-null and not overlapping.
+#[allow(unsafe_code)]
+#[no_mangle]
+pub unsafe extern "C" fn dusk_poseidon_hash(
+    input: *const u8,
+    input_len: usize,
+    output: *mut u8,
+) -> u32 {
+    let input = slice::from_raw_parts(input, input_len);
+    poseidon_hash(input, &mut *output.cast())
+}
+
+/// Hash the scalars encoded in `input` with [`Domain::Other`] and write the
+/// encoded hash, truncated to a jubjub scalar, into `output`.
+///
+/// The length of `input` needs to be a non-zero multiple of 32 encoding at
+/// most [`MAX_INPUT_SCALARS`] scalars.
+pub fn poseidon_hash_truncated(
+    input: &[u8],
+    output: &mut [u8; SCALAR_SIZE],
+) -> u32 {
+    match hash(input) {
+        Ok(hash) => {
+            output.copy_from_slice(&truncate(&hash).to_bytes());
+            SUCCESS
+        }
+        Err(code) => code,
+    }
+}
+
+/// The C ABI of [`poseidon_hash_truncated`], with the input being the
+/// `input_len` bytes at `input` and the output the 32 bytes at `output`.
+///
+/// # Safety
+/// `input` needs to be valid for reads of `input_len` bytes and `output`
+/// valid for writes of 32 bytes, both non-null and not overlapping.
+#[allow(unsafe_code)]
+#[no_mangle]
+pub unsafe extern "C" fn dusk_poseidon_hash_truncated(
+    input: *const u8,
+    input_len: usize,
+    output: *mut u8,
+) -> u32 {
+    let input = slice::from_raw_parts(input, input_len);
+    poseidon_hash_truncated(input, &mut *output.cast())
+}
+
+// Check that the leaves encode a non-zero number of scalars that fit into a
+// tree of arity 4 and the given height, and return that number.
+fn leaves_count(leaves: &[u8], height: u32) -> Result<u64, u32> {
+    if height > MAX_MERKLE_HEIGHT
+        || leaves.is_empty()
+        || leaves.len() % SCALAR_SIZE != 0
+    {
+        return Err(INVALID_INPUT_LENGTH);
+    }
+    // the number of positions fits into a u128 for every allowed height
+    let positions = (MAX_MERKLE_ARITY as u128).pow(height);
+    let len = leaves.len() / SCALAR_SIZE;
+    match len as u128 > positions {
+        true => Err(INVALID_INPUT_LENGTH),
+        false => Ok(len as u64),
+    }
+}
+
+// Decode the leaves as they are consumed. The code of the first invalid leaf
+// is recorded into `result` and the leaf replaced by zero, so that the number
+// of leaves stays the same.
+fn decode_leaves<'a>(
+    leaves: &'a [u8],
+    result: &'a mut Result<(), u32>,
+) -> impl Iterator<Item = BlsScalar> + 'a {
+    leaves.chunks(SCALAR_SIZE).map(|chunk| {
+        decode(chunk).unwrap_or_else(|code| {
+            if result.is_ok() {
+                *result = Err(code);
+            }
+            BlsScalar::zero()
+        })
+    })
+}
+
+// Compute the root of the merkle tree of arity 4 and the given height from
+// the leaves encoded in `leaves`.
+fn merkle_root(leaves: &[u8], height: u32) -> Result<BlsScalar, u32> {
+    leaves_count(leaves, height)?;
+
+    let mut result = Ok(());
+    let root = root_from_leaves::<MAX_MERKLE_ARITY>(
+        height as usize,
+        decode_leaves(leaves, &mut result),
+    );
+    result.map(|()| root)
+}
+
+/// Compute the root of a merkle tree of arity 4 and the given height from the
+/// scalars encoded in `leaves`, in order of their positions, and write the
+/// encoded root into `output`.
+///
+/// The positions after the last leaf are empty, see
+/// [`root_from_leaves`](crate::merkle::root_from_leaves). The leaves are
+/// hashed as they are decoded, so their number isn't bounded by
+/// [`MAX_INPUT_SCALARS`], but the length of `leaves` needs to be a non-zero
+/// multiple of 32, the height can't exceed [`MAX_MERKLE_HEIGHT`] and the tree
+/// needs to have a position for every leaf.
+pub fn poseidon_merkle_root(
+    leaves: &[u8],
+    height: u32,
+    output: &mut [u8; SCALAR_SIZE],
+) -> u32 {
+    match merkle_root(leaves, height) {
+        Ok(root) => {
+            output.copy_from_slice(&root.to_bytes());
+            SUCCESS
+        }
+        Err(code) => code,
+    }
+}
+
+/// The C ABI of [`poseidon_merkle_root`], with the leaves being the
+/// `leaves_len` bytes at `leaves` and the output the 32 bytes at `output`.
+///
+/// # Safety
+/// `leaves` needs to be valid for reads of `leaves_len` bytes and `output`
+/// valid for writes of 32 bytes, both non-null and not overlapping.
+#[allow(unsafe_code)]
+#[no_mangle]
+pub unsafe extern "C" fn dusk_poseidon_merkle_root(
+    leaves: *const u8,
+    leaves_len: usize,
+    height: u32,
+    output: *mut u8,
+) -> u32 {
+    let leaves = slice::from_raw_parts(leaves, leaves_len);
+    poseidon_merkle_root(leaves, height, &mut *output.cast())
+}
+
+// The size of the encoding of one level of an opening.
+const OPENING_LEVEL_SIZE: usize = MAX_MERKLE_ARITY * SCALAR_SIZE;
+
+// Compute the opening of the leaf at the position of the merkle tree of arity
+// 4 and the given height from the leaves encoded in `leaves` into `output`.
+fn merkle_opening(
+    leaves: &[u8],
+    height: u32,
+    position: u64,
+    output: &mut [u8],
+) -> Result<(), u32> {
+    if position >= leaves_count(leaves, height)? {
+        return Err(INVALID_POSITION);
+    }
+    if output.len() != height as usize * OPENING_LEVEL_SIZE {
+        return Err(INVALID_OUTPUT_LENGTH);
+    }
+
+    let mut result = Ok(());
+    let opening = opening::<MAX_MERKLE_ARITY>(
+        height as usize,
+        decode_leaves(leaves, &mut result),
+        position,
+    );
+    result?;
+
+    output
+        .chunks_exact_mut(SCALAR_SIZE)
+        .zip(opening.iter().flatten())
+        .for_each(|(chunk, node)| chunk.copy_from_slice(&node.to_bytes()));
+    Ok(())
+}
+
+/// Compute the opening of the leaf at the position of a merkle tree of arity
+/// 4 and the given height from the scalars encoded in `leaves`, in order of
+/// their positions, and write the encoded opening into `output`.
+///
+/// The opening is encoded as the 4 nodes of every level on the path from the
+/// leaf to the root, starting with the level of the leaf, see
+/// [`opening`](crate::merkle::opening), so `output` needs to be exactly
+/// `height * 128` bytes long. The leaves are the same as with
+/// [`poseidon_merkle_root`], and there needs to be a leaf at the position.
+pub fn poseidon_merkle_opening(
+    leaves: &[u8],
+    height: u32,
+    position: u64,
+    output: &mut [u8],
+) -> u32 {
+    match merkle_opening(leaves, height, position, output) {
+        Ok(()) => SUCCESS,
+        Err(code) => code,
+    }
+}
+
+/// The C ABI of [`poseidon_merkle_opening`], with the leaves being the
+/// `leaves_len` bytes at `leaves` and the output the `output_len` bytes at
+/// `output`.
+///
+/// # Safety
+/// `leaves` needs to be valid for reads of `leaves_len` bytes and `output`
+/// valid for writes of `output_len` bytes, both non-null and not
+/// overlapping.
+#[allow(unsafe_code)]
+#[no_mangle]
+pub unsafe extern "C" fn dusk_poseidon_merkle_opening(
+    leaves: *const u8,
+    leaves_len: usize,
+    height: u32,
+    position: u64,
+    output: *mut u8,
+    output_len: usize,
+) -> u32 {
+    let leaves = slice::from_raw_parts(leaves, leaves_len);
+    let output = slice::from_raw_parts_mut(output, output_len);
+    poseidon_merkle_opening(leaves, height, position, output)
+}
+
+// Verify the opening encoded in `opening` of the leaf at the position against
+// the root.
+fn merkle_verify(
+    root: &[u8; SCALAR_SIZE],
+    leaf: &[u8; SCALAR_SIZE],
+    position: u64,
+    opening: &[u8],
+) -> Result<(), u32> {
+    let height = opening.len() / OPENING_LEVEL_SIZE;
+    if opening.len() % OPENING_LEVEL_SIZE != 0
+        || height > MAX_MERKLE_HEIGHT as usize
+    {
+        return Err(INVALID_INPUT_LENGTH);
+    }
+    let root = decode(root)?;
+    let leaf = decode(leaf)?;
+
+    let mut levels =
+        [[BlsScalar::zero(); MAX_MERKLE_ARITY]; MAX_MERKLE_HEIGHT as usize];
+    for (nodes, bytes) in
+        levels.iter_mut().zip(opening.chunks(OPENING_LEVEL_SIZE))
+    {
+        for (node, chunk) in nodes.iter_mut().zip(bytes.chunks(SCALAR_SIZE)) {
+            *node = decode(chunk)?;
+        }
+    }
+
+    match verify_opening(&root, &leaf, position, &levels[..height]) {
+        true => Ok(()),
+        false => Err(INVALID_OPENING),
+    }
+}
+
+/// Verify the opening encoded in `opening`, as written by
+/// [`poseidon_merkle_opening`], of the encoded leaf at the position against
+/// the encoded root of a merkle tree of arity 4.
+///
+/// The height of the tree is given by the length of `opening`, which needs
+/// to be a multiple of 128 of at most [`MAX_MERKLE_HEIGHT`] levels. Returns
+/// [`SUCCESS`] when the opening is valid and [`INVALID_OPENING`] when it
+/// isn't.
+pub fn poseidon_merkle_verify(
+    root: &[u8; SCALAR_SIZE],
+    leaf: &[u8; SCALAR_SIZE],
+    position: u64,
+    opening: &[u8],
+) -> u32 {
+    match merkle_verify(root, leaf, position, opening) {
+        Ok(()) => SUCCESS,
+        Err(code) => code,
+    }
+}
+
+/// The C ABI of [`poseidon_merkle_verify`], with the root and the leaf being
+/// the 32 bytes at `root` and `leaf` and the opening the `opening_len` bytes
+/// at `opening`.
+///
+/// # Safety
+/// `root` and `leaf` need to be valid for reads of 32 bytes each and
+/// `opening` valid for reads of `opening_len` bytes, all non-null.
+#[allow(unsafe_code)]
+#[no_mangle]
+pub unsafe extern "C" fn dusk_poseidon_merkle_verify(
+    root: *const u8,
+    leaf: *const u8,
+    position: u64,
+    opening: *const u8,
+    opening_len: usize,
+) -> u32 {
+    let opening = slice::from_raw_parts(opening, opening_len);
+    poseidon_merkle_verify(&*root.cast(), &*leaf.cast(), position, opening)
+}
+
+// Decode the shared secret and the nonce of the encryption.
+#[cfg(feature = "encryption")]
+fn decode_secret(
+    shared_secret: &[u8; 32],
+    nonce: &[u8; SCALAR_SIZE],
+) -> Result<(JubJubAffine, Nonce), u32> {
+    let shared_secret = Option::from(JubJubAffine::from_bytes(*shared_secret))
+        .ok_or(INVALID_POINT)?;
+    let nonce = Nonce::from_bytes(nonce).ok_or(INVALID_SCALAR)?;
+    Ok((shared_secret, nonce))
+}
+
+// Encrypt the message encoded in `message` into `output`.
+#[cfg(feature = "encryption")]
+fn encrypt(
+    message: &[u8],
+    shared_secret: &[u8; 32],
+    nonce: &[u8; SCALAR_SIZE],
+    output: &mut [u8],
+) -> Result<(), u32> {
+    let mut buffer = [BlsScalar::zero(); MAX_INPUT_SCALARS];
+    let len = decode_into(message, &mut buffer)?;
+    if output.len() != (len + 1) * SCALAR_SIZE {
+        return Err(INVALID_OUTPUT_LENGTH);
+    }
+    let (shared_secret, nonce) = decode_secret(shared_secret, nonce)?;
+
+    let cipher = crate::encrypt(&buffer[..len], shared_secret, &nonce)
+        .map_err(|error| error.code())?;
+    encode_into(&cipher, output);
+    Ok(())
+}
+
+/// Encrypt the scalars encoded in `message` with the encoded shared secret
+/// and nonce, and write the encoded cipher into `output`.
+///
+/// The length of `message` needs to be a non-zero multiple of 32 encoding at
+/// most [`MAX_INPUT_SCALARS`] scalars, and `output` needs to be exactly 32
+/// bytes longer than `message`.
+#[cfg(feature = "encryption")]
+pub fn poseidon_encrypt(
+    message: &[u8],
+    shared_secret: &[u8; 32],
+    nonce: &[u8; SCALAR_SIZE],
+    output: &mut [u8],
+) -> u32 {
+    match encrypt(message, shared_secret, nonce, output) {
+        Ok(()) => SUCCESS,
+        Err(code) => code,
+    }
+}
+
+/// The C ABI of [`poseidon_encrypt`], with the message being the
+/// `message_len` bytes at `message`, the shared secret and the nonce the 32
+/// bytes at `shared_secret` and `nonce`, and the output the `output_len` bytes
+/// at `output`.
+///
+/// # Safety
+/// `message` needs to be valid for reads of `message_len` bytes,
+/// `shared_secret` and `nonce` valid for reads of 32 bytes each and `output`
+/// valid for writes of `output_len` bytes, all non-null and `output` not
+/// overlapping any of the others.
+#[cfg(feature = "encryption")]
+#[allow(unsafe_code)]
+#[no_mangle]
+pub unsafe extern "C" fn dusk_poseidon_encrypt(
+    message: *const u8,
+    message_len: usize,
+    shared_secret: *const u8,
+    nonce: *const u8,
+    output: *mut u8,
+    output_len: usize,
+) -> u32 {
+    let message = slice::from_raw_parts(message, message_len);
+    let output = slice::from_raw_parts_mut(output, output_len);
+    poseidon_encrypt(message, &*shared_secret.cast(), &*nonce.cast(), output)
+}
+
+// Decrypt the cipher encoded in `cipher` into `output`.
+#[cfg(feature = "encryption")]
+fn decrypt(
+    cipher: &[u8],
+    shared_secret: &[u8; 32],
+    nonce: &[u8; SCALAR_SIZE],
+    output: &mut [u8],
+) -> Result<(), u32> {
+    let mut buffer = [BlsScalar::zero(); MAX_INPUT_SCALARS];
+    let len = match decode_into(cipher, &mut buffer)? {
+        len if len > 1 => len,
+        _ => return Err(INVALID_INPUT_LENGTH),
+    };
+    if output.len() != (len - 1) * SCALAR_SIZE {
+        return Err(INVALID_OUTPUT_LENGTH);
+    }
+    let (shared_secret, nonce) = decode_secret(shared_secret, nonce)?;
+
+    let message = crate::decrypt(&buffer[..len], shared_secret, &nonce)
+        .map_err(|error| error.code())?;
+    encode_into(&message, output);
+    Ok(())
+}
+
+/// Decrypt the scalars encoded in `cipher` with the encoded shared secret
+/// and nonce, and write the encoded message into `output`.
+///
+/// The length of `cipher` needs to be a multiple of 32 encoding at least two
+/// and at most [`MAX_INPUT_SCALARS`] scalars, and `output` needs to be
+/// exactly 32 bytes shorter than `cipher`.
+#[cfg(feature = "encryption")]
+pub fn poseidon_decrypt(
+    cipher: &[u8],
+    shared_secret: &[u8; 32],
+    nonce: &[u8; SCALAR_SIZE],
+    output: &mut [u8],
+) -> u32 {
+    match decrypt(cipher, shared_secret, nonce, output) {
+        Ok(()) => SUCCESS,
+        Err(code) => code,
+    }
+}
+
+/// The C ABI of [`poseidon_decrypt`], with the cipher being the `cipher_len`
+/// bytes at `cipher`, the shared secret and the nonce the 32 bytes at
+/// `shared_secret` and `nonce`, and the output the `output_len` bytes at
+/// `output`.
+///
+/// # Safety
+/// `cipher` needs to be valid for reads of `cipher_len` bytes,
+/// `shared_secret` and `nonce` valid for reads of 32 bytes each and `output`
+/// valid for writes of `output_len` bytes, all non-null and `output` not
+/// overlapping any of the others.
+#[cfg(feature = "encryption")]
+#[allow(unsafe_code)]
+#[no_mangle]
+pub unsafe extern "C" fn dusk_poseidon_decrypt(
+    cipher: *const u8,
+    cipher_len: usize,
+    shared_secret: *const u8,
+    nonce: *const u8,
+    output: *mut u8,
+    output_len: usize,
+) -> u32 {
+    let cipher = slice::from_raw_parts(cipher, cipher_len);
+    let output = slice::from_raw_parts_mut(output, output_len);
+    poseidon_decrypt(cipher, &*shared_secret.cast(), &*nonce.cast(), output)
+}
@@ -0,0 +1,88 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use blake2b_simd::{Params, State};
+use dusk_bls12_381::BlsScalar;
+
+use crate::{Domain, Hash};
+
+/// The size of the Blake2b digest of the [`HybridHasher`].
+const BLAKE2B_SIZE: usize = 32;
+
+/// The digests computed by the [`HybridHasher`] over the same input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HybridDigest {
+    /// The Poseidon digest, as computed by [`Hash::digest`].
+    pub poseidon: BlsScalar,
+    /// The 32 byte Blake2b digest of the canonical encoding of the input.
+    pub blake2b: [u8; BLAKE2B_SIZE],
+}
+
+/// Hash the same input with Poseidon and Blake2b in one pass.
+///
+/// The Poseidon digest is meant for circuits, and the cheaper Blake2b digest
+/// for anything outside of them. Since both digests are computed from the
+/// same calls to [`HybridHasher::update`], the input they commit to can't
+/// diverge.
+///
+/// The Blake2b input is the domain-separator, encoded as 8 little-endian
+/// bytes, followed by the canonical 32 byte encoding of each input scalar.
+pub struct HybridHasher<'a> {
+    poseidon: Hash<'a>,
+    blake2b: State,
+}
+
+impl<'a> HybridHasher<'a> {
+    /// Create a new hybrid hasher.
+    pub fn new(domain: Domain) -> Self {
+        let mut blake2b = Params::new().hash_length(BLAKE2B_SIZE).to_state();
+        blake2b.update(&u64::from(domain).to_le_bytes());
+
+        Self {
+            poseidon: Hash::new(domain),
+            blake2b,
+        }
+    }
+
+    /// Update the input of both hashes.
+    ///
+    /// Returns a mutable reference to the hasher to allow chaining calls.
+    pub fn update(&mut self, input: &'a [BlsScalar]) -> &mut Self {
+        self.poseidon.update(input);
+        input.iter().for_each(|scalar| {
+            self.blake2b.update(&scalar.to_bytes());
+        });
+        self
+    }
+
+    /// Finalize both hashes.
+    ///
+    /// # Panics
+    /// This function panics when the io-pattern can not be created with the
+    /// given domain and input, e.g. using [`Domain::Merkle4`] with an input
+    /// anything other than 4 Scalar.
+    pub fn finalize(&self) -> HybridDigest {
+        let mut blake2b = [0u8; BLAKE2B_SIZE];
+        blake2b.copy_from_slice(self.blake2b.finalize().as_bytes());
+
+        HybridDigest {
+            poseidon: self.poseidon.finalize()[0],
+            blake2b,
+        }
+    }
+
+    /// Digest an input with both hashes immediately.
+    ///
+    /// # Panics
+    /// This function panics when the io-pattern can not be created with the
+    /// given domain and input, e.g. using [`Domain::Merkle4`] with an input
+    /// anything other than 4 Scalar.
+    pub fn digest(domain: Domain, input: &'a [BlsScalar]) -> HybridDigest {
+        let mut hasher = Self::new(domain);
+        hasher.update(input);
+        hasher.finalize()
+    }
+}
@@ -0,0 +1,48 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#[cfg(feature = "zk")]
+pub(crate) mod gadget;
+
+use alloc::vec::Vec;
+
+use dusk_bls12_381::BlsScalar;
+
+use crate::hash::{SeparatedDomain, LEAF_SEPARATOR};
+use crate::Hash;
+
+// The domain of the leaf hashes, with the number of metadata fields added to
+// the separator.
+pub(crate) fn leaf_domain(meta_len: usize) -> SeparatedDomain {
+    SeparatedDomain(LEAF_SEPARATOR + meta_len as u64)
+}
+
+/// Hash the scalars of a leaf together with its `u64` metadata fields, e.g.
+/// positions, expirations or types, into one scalar.
+///
+/// Each metadata field is encoded as the scalar of the same value and
+/// absorbed after the scalars. The number of metadata fields is encoded in
+/// the domain-separator, so that the boundary between scalars and metadata
+/// can't be shifted.
+/// The same hash can be computed in a circuit with
+/// [`leaf_hash_gadget`](crate::leaf_hash_gadget).
+///
+/// # Panics
+/// This function panics when both the scalars and the metadata are empty.
+pub fn leaf_hash(scalars: &[BlsScalar], meta: &[u64]) -> BlsScalar {
+    let meta: Vec<BlsScalar> =
+        meta.iter().map(|field| BlsScalar::from(*field)).collect();
+
+    let mut hash = Hash::new(leaf_domain(meta.len()));
+    // empty inputs are not absorbed, the sponge rejects absorbing nothing
+    [scalars, &meta[..]]
+        .into_iter()
+        .filter(|input| !input.is_empty())
+        .for_each(|input| {
+            hash.update(input);
+        });
+    hash.finalize()[0]
+}
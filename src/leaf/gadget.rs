@@ -0,0 +1,59 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_plonk::prelude::{Composer, Witness};
+
+use crate::gadgets::decompose;
+use crate::HashGadget;
+
+use super::leaf_domain;
+
+/// Hash the scalar witnesses of a leaf together with its metadata witnesses
+/// in the circuit, the same way as with [`leaf_hash`](crate::leaf_hash).
+///
+/// Each metadata witness is asserted to fit into 64 bits, so that it is the
+/// canonical encoding of a `u64`.
+///
+/// # Panics
+/// This function panics when both the scalars and the metadata are empty.
+pub fn leaf_hash_gadget(
+    composer: &mut Composer,
+    scalars: &[Witness],
+    meta: &[Witness],
+) -> Witness {
+    meta.iter().for_each(|field| {
+        decompose(composer, *field, 64);
+    });
+
+    let mut hash = HashGadget::new(leaf_domain(meta.len()));
+    // empty inputs are not absorbed, the sponge rejects absorbing nothing
+    [scalars, meta]
+        .into_iter()
+        .filter(|input| !input.is_empty())
+        .for_each(|input| {
+            hash.update(input);
+        });
+    hash.finalize(composer)[0]
+}
+
+/// Assert that the expected witness is the leaf hash of the field witnesses,
+/// the same as [`leaf_hash`](crate::leaf_hash) of the fields without
+/// metadata.
+///
+/// This recomputes a leaf from its fields, the first step of proving its
+/// membership, in the leaf domain, so that leaves can't be mixed up with
+/// hashes of any other domain.
+///
+/// # Panics
+/// This function panics when the fields are empty.
+pub fn assert_leaf_hash(
+    composer: &mut Composer,
+    fields: &[Witness],
+    expected_leaf: Witness,
+) {
+    let leaf = leaf_hash_gadget(composer, fields, &[]);
+    composer.assert_equal(leaf, expected_leaf);
+}
@@ -7,6 +7,8 @@
 #![no_std]
 #![doc = include_str!("../README.md")]
 #![deny(missing_docs)]
+#![cfg_attr(not(feature = "hostfn"), forbid(unsafe_code))]
+#![cfg_attr(feature = "hostfn", deny(unsafe_code))]
 
 extern crate alloc;
 
@@ -14,18 +16,98 @@ mod error;
 pub use error::Error;
 
 mod hades;
+pub use hades::ScalarPermutation;
+pub use hades::CAPACITY as HADES_CAPACITY;
+pub use hades::RATE as HADES_RATE;
 pub use hades::WIDTH as HADES_WIDTH;
+#[cfg(feature = "zk")]
+pub use hades::{
+    BackendPermutation, GadgetBackend, GadgetPermutation, HadesCircuitBackend,
+};
+
+/// The largest arity of a merkle tree level that is hashed with a single
+/// permutation, which is the arity of [`Domain::Merkle4`].
+pub const MAX_MERKLE_ARITY: usize = HADES_RATE;
 
 mod hash;
 #[cfg(feature = "zk")]
-pub use hash::gadget::HashGadget;
-pub use hash::{Domain, Hash};
+pub use hades::ConstantCache;
+#[cfg(feature = "zk")]
+pub use hash::gadget::{
+    assert_digest_eq, compress_gadget, embed_jubjub_gadget, level_hash_gadget,
+    HashGadget,
+};
+pub use hash::{
+    compress, embed_jubjub, hash_rows, is_reserved_encoding, level_hash,
+    Domain, DomainKind, Hash, RESERVED_ENCODINGS,
+};
 
 #[cfg(feature = "encryption")]
 mod encryption;
 
 #[cfg(feature = "encryption")]
 #[cfg(feature = "zk")]
-pub use encryption::gadget::{decrypt_gadget, encrypt_gadget};
+pub use encryption::gadget::{
+    assert_nonce_derived, decrypt_gadget, derive_key_gadget, encrypt_gadget,
+    encrypt_gadget_pi, initial_state_gadget, reencrypt_gadget,
+    verify_cipher_gadget,
+};
 #[cfg(feature = "encryption")]
-pub use encryption::{decrypt, encrypt};
+pub use encryption::{
+    decrypt, derive_key, encrypt, initial_state, reencrypt, Nonce,
+};
+
+#[cfg(feature = "zk")]
+pub mod gadgets;
+
+mod range;
+#[cfg(feature = "zk")]
+pub use range::gadget::assert_range_committed;
+pub use range::{range_commitment, MAX_RANGE_BITS, RANGE_DIGIT_BITS};
+
+pub mod compat;
+
+pub mod convert;
+
+pub mod features;
+
+pub mod merkle;
+
+pub mod pset;
+
+mod leaf;
+#[cfg(feature = "zk")]
+pub use leaf::gadget::{assert_leaf_hash, leaf_hash_gadget};
+pub use leaf::leaf_hash;
+
+mod position;
+#[cfg(feature = "zk")]
+pub use position::gadget::position_of_gadget;
+pub use position::position_of;
+
+mod bind;
+pub use bind::bind_proof;
+
+mod derive;
+pub use derive::derive_child;
+#[cfg(feature = "zk")]
+pub use derive::gadget::derive_child_gadget;
+
+#[cfg(feature = "hostfn")]
+pub mod hostfn;
+
+#[cfg(feature = "hybrid")]
+mod hybrid;
+#[cfg(feature = "hybrid")]
+pub use hybrid::{HybridDigest, HybridHasher};
+
+#[cfg(feature = "metrics")]
+pub mod metrics;
+
+#[cfg(feature = "std-hash")]
+mod hasher;
+#[cfg(feature = "std-hash")]
+pub use hasher::{PoseidonBuildHasher, PoseidonHasher};
+
+#[cfg(feature = "zk")]
+pub mod report;
@@ -0,0 +1,185 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Computation of merkle roots without materializing the tree.
+//!
+//! The levels of the tree are hashed with [`level_hash`], and empty subtrees
+//! are represented by the zero element on every level, the same as the empty
+//! slots of a level.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use dusk_bls12_381::BlsScalar;
+
+use crate::level_hash;
+
+/// Compute the root of a tree of the given height and arity `A` from its
+/// leaves, in order of their positions.
+///
+/// The leaves are consumed in a single pass, holding at most `A` nodes per
+/// level in memory. Any positions after the last leaf are empty.
+///
+/// # Panics
+/// This function panics when the arity is neither 2 nor 4, or when there are
+/// more leaves than the tree has positions.
+pub fn root_from_leaves<const A: usize>(
+    height: usize,
+    leaves: impl IntoIterator<Item = BlsScalar>,
+) -> BlsScalar {
+    fold::<A>(height, leaves, |_, _, _| {})
+}
+
+/// Compute the opening of the leaf at the given position of a tree of the
+/// given height and arity `A` from its leaves, in order of their positions.
+///
+/// The opening holds the `A` nodes of every level on the path from the leaf
+/// to the root, starting with the level of the leaf, and is checked against
+/// the root with [`verify_opening`]. The leaves are consumed in a single pass
+/// the same as with [`root_from_leaves`].
+///
+/// # Panics
+/// This function panics when the arity is neither 2 nor 4, when there are
+/// more leaves than the tree has positions, or when there is no leaf at the
+/// position.
+pub fn opening<const A: usize>(
+    height: usize,
+    leaves: impl IntoIterator<Item = BlsScalar>,
+    position: u64,
+) -> Vec<[BlsScalar; A]> {
+    let mut leaves_len = 0u64;
+    let leaves = leaves.into_iter().inspect(|_| leaves_len += 1);
+
+    let mut opening = vec![[BlsScalar::zero(); A]; height];
+    fold::<A>(height, leaves, |level, index, nodes| {
+        if index == parent_position::<A>(position, level) {
+            opening[level] = *nodes;
+        }
+    });
+    assert!(position < leaves_len, "there is no leaf at the position");
+
+    opening
+}
+
+/// Check that the opening of the leaf at the given position, as computed
+/// with [`opening`], leads to the root.
+///
+/// # Panics
+/// This function panics when the arity is neither 2 nor 4.
+pub fn verify_opening<const A: usize>(
+    root: &BlsScalar,
+    leaf: &BlsScalar,
+    position: u64,
+    opening: &[[BlsScalar; A]],
+) -> bool {
+    let mut node = *leaf;
+    for (level, nodes) in opening.iter().enumerate() {
+        let offset = match level {
+            0 => position,
+            _ => parent_position::<A>(position, level - 1),
+        } % A as u64;
+        if nodes[offset as usize] != node {
+            return false;
+        }
+        node = level_hash(nodes);
+    }
+
+    // the position needs to be within the tree
+    parent_position::<A>(position, opening.len().wrapping_sub(1)) == 0
+        && node == *root
+}
+
+// The position of the parent of the node at the position on the level above
+// the given one, i.e. the index of the group of `A` nodes on the given level
+// holding that node.
+fn parent_position<const A: usize>(position: u64, level: usize) -> u64 {
+    let shift = A.trailing_zeros() as usize * level.wrapping_add(1);
+    match shift < 64 {
+        true => position >> shift,
+        false => 0,
+    }
+}
+
+// Fold the leaves into the root of the tree, calling `on_level` with the
+// level, the index and the nodes of every group of `A` nodes hashed into
+// their parent.
+fn fold<const A: usize>(
+    height: usize,
+    leaves: impl IntoIterator<Item = BlsScalar>,
+    mut on_level: impl FnMut(usize, u64, &[BlsScalar; A]),
+) -> BlsScalar {
+    // the nodes of each level that haven't been hashed into their parent yet,
+    // and the number of groups of each level hashed so far. the last level
+    // holds the root.
+    let mut levels: Vec<Vec<BlsScalar>> =
+        (0..=height).map(|_| Vec::with_capacity(A)).collect();
+    let mut hashed = vec![0u64; height];
+
+    for leaf in leaves {
+        push_node::<A>(&mut levels, &mut hashed, &mut on_level, 0, leaf);
+    }
+
+    // hash the partially filled levels with their empty slots
+    for level in 0..height {
+        if !levels[level].is_empty() {
+            levels[level].resize(A, BlsScalar::zero());
+            let node =
+                hash_level::<A>(&mut levels, &mut hashed, &mut on_level, level);
+            push_node::<A>(
+                &mut levels,
+                &mut hashed,
+                &mut on_level,
+                level + 1,
+                node,
+            );
+        }
+    }
+
+    levels[height].first().copied().unwrap_or(BlsScalar::zero())
+}
+
+// Push the node into the level, hashing the level into its parent when it's
+// full.
+fn push_node<const A: usize>(
+    levels: &mut [Vec<BlsScalar>],
+    hashed: &mut [u64],
+    on_level: &mut impl FnMut(usize, u64, &[BlsScalar; A]),
+    mut level: usize,
+    mut node: BlsScalar,
+) {
+    let height = levels.len() - 1;
+    loop {
+        if level == height {
+            assert!(
+                levels[height].is_empty(),
+                "the tree has more leaves than positions"
+            );
+            levels[height].push(node);
+            return;
+        }
+
+        levels[level].push(node);
+        if levels[level].len() < A {
+            return;
+        }
+        node = hash_level::<A>(levels, hashed, on_level, level);
+        level += 1;
+    }
+}
+
+// Hash the full level and clear it.
+fn hash_level<const A: usize>(
+    levels: &mut [Vec<BlsScalar>],
+    hashed: &mut [u64],
+    on_level: &mut impl FnMut(usize, u64, &[BlsScalar; A]),
+    level: usize,
+) -> BlsScalar {
+    let nodes: [BlsScalar; A] = core::array::from_fn(|i| levels[level][i]);
+    levels[level].clear();
+    on_level(level, hashed[level], &nodes);
+    hashed[level] += 1;
+    level_hash(&nodes)
+}
@@ -0,0 +1,236 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Instrumentation of the Poseidon workload computed outside of circuits.
+//!
+//! The workload is reported to a [`MetricsSink`] passed by the caller, so
+//! that node operators can attribute the time spent in the permutation to
+//! the hashes and tree levels that caused it. Only the metered entry points,
+//! [`Hash::finalize_metered`](crate::Hash::finalize_metered) and the
+//! functions of this module, report to a sink. Permutations appended to a
+//! circuit by the gadgets are not reported.
+//!
+//! Since every caller passes its own sink, the workload can be scoped as
+//! needed, e.g. per request with [`Counters`] or per process with
+//! [`AtomicCounters`].
+
+use alloc::vec::Vec;
+use core::cell::Cell;
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use dusk_bls12_381::BlsScalar;
+use dusk_safe::Safe;
+
+use crate::hades::WIDTH;
+use crate::DomainKind;
+
+/// A receiver of the events of the native hashing workload.
+///
+/// All methods default to doing nothing, so that a sink only implements the
+/// events it is interested in.
+pub trait MetricsSink {
+    /// Called for every Hades permutation performed.
+    fn record_permutation(&self) {}
+
+    /// Called for every finalized hash.
+    fn record_hash(&self) {}
+
+    /// Called for every tree level hashed with [`level_hash`].
+    fn record_level(&self) {}
+
+    /// Called for every hash of [`hash_rows`] that reused the tag of a
+    /// previous row.
+    fn record_tag_cache_hit(&self) {}
+}
+
+// Wraps a permutation and reports every permutation to the sink.
+pub(crate) struct Metered<'a, P> {
+    permutation: P,
+    sink: &'a dyn MetricsSink,
+}
+
+impl<'a, P> Metered<'a, P> {
+    pub fn new(permutation: P, sink: &'a dyn MetricsSink) -> Self {
+        Self { permutation, sink }
+    }
+}
+
+impl<'a, P> Safe<BlsScalar, WIDTH> for Metered<'a, P>
+where
+    P: Safe<BlsScalar, WIDTH>,
+{
+    fn permute(&mut self, state: &mut [BlsScalar; WIDTH]) {
+        self.sink.record_permutation();
+        self.permutation.permute(state);
+    }
+
+    fn tag(&mut self, input: &[u8]) -> BlsScalar {
+        self.permutation.tag(input)
+    }
+
+    fn add(&mut self, right: &BlsScalar, left: &BlsScalar) -> BlsScalar {
+        self.permutation.add(right, left)
+    }
+}
+
+/// A snapshot of the counters of a sink.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Metrics {
+    /// The number of Hades permutations performed.
+    pub permutations: u64,
+    /// The number of finalized hashes.
+    pub hashes: u64,
+    /// The number of tree levels hashed with [`level_hash`].
+    pub levels: u64,
+    /// The number of hashes of [`hash_rows`] that reused the tag of a
+    /// previous row.
+    pub tag_cache_hits: u64,
+}
+
+/// A sink counting the events of the workload of a single caller.
+#[derive(Debug, Default)]
+pub struct Counters {
+    metrics: Cell<Metrics>,
+}
+
+impl Counters {
+    /// Create new counters starting at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return a snapshot of the current value of the counters.
+    pub fn snapshot(&self) -> Metrics {
+        self.metrics.get()
+    }
+
+    /// Reset all counters to zero and return their value before the reset.
+    pub fn reset(&self) -> Metrics {
+        self.metrics.take()
+    }
+
+    fn update(&self, f: impl FnOnce(&mut Metrics)) {
+        let mut metrics = self.metrics.get();
+        f(&mut metrics);
+        self.metrics.set(metrics);
+    }
+}
+
+impl MetricsSink for Counters {
+    fn record_permutation(&self) {
+        self.update(|m| m.permutations += 1);
+    }
+
+    fn record_hash(&self) {
+        self.update(|m| m.hashes += 1);
+    }
+
+    fn record_level(&self) {
+        self.update(|m| m.levels += 1);
+    }
+
+    fn record_tag_cache_hit(&self) {
+        self.update(|m| m.tag_cache_hits += 1);
+    }
+}
+
+/// A sink counting the events of the workload of several threads, e.g. held
+/// in a `static` for the workload of the whole process.
+///
+/// Only available on targets supporting 64 bit atomics.
+#[cfg(target_has_atomic = "64")]
+#[derive(Debug, Default)]
+pub struct AtomicCounters {
+    permutations: AtomicU64,
+    hashes: AtomicU64,
+    levels: AtomicU64,
+    tag_cache_hits: AtomicU64,
+}
+
+#[cfg(target_has_atomic = "64")]
+impl AtomicCounters {
+    /// Create new counters starting at zero.
+    pub const fn new() -> Self {
+        Self {
+            permutations: AtomicU64::new(0),
+            hashes: AtomicU64::new(0),
+            levels: AtomicU64::new(0),
+            tag_cache_hits: AtomicU64::new(0),
+        }
+    }
+
+    /// Return a snapshot of the current value of the counters.
+    pub fn snapshot(&self) -> Metrics {
+        Metrics {
+            permutations: self.permutations.load(Ordering::Relaxed),
+            hashes: self.hashes.load(Ordering::Relaxed),
+            levels: self.levels.load(Ordering::Relaxed),
+            tag_cache_hits: self.tag_cache_hits.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Reset all counters to zero and return their value before the reset.
+    pub fn reset(&self) -> Metrics {
+        Metrics {
+            permutations: self.permutations.swap(0, Ordering::Relaxed),
+            hashes: self.hashes.swap(0, Ordering::Relaxed),
+            levels: self.levels.swap(0, Ordering::Relaxed),
+            tag_cache_hits: self.tag_cache_hits.swap(0, Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(target_has_atomic = "64")]
+impl MetricsSink for AtomicCounters {
+    fn record_permutation(&self) {
+        self.permutations.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_hash(&self) {
+        self.hashes.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_level(&self) {
+        self.levels.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_tag_cache_hit(&self) {
+        self.tag_cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// The same as [`level_hash`](crate::level_hash), reporting the workload to
+/// the sink.
+pub fn level_hash<const A: usize>(
+    level: &[BlsScalar; A],
+    sink: &dyn MetricsSink,
+) -> BlsScalar {
+    crate::hash::metered_level_hash(level, sink)
+}
+
+/// The same as [`compress`](crate::compress), reporting the workload to the
+/// sink.
+pub fn compress(
+    a: &BlsScalar,
+    b: &BlsScalar,
+    sink: &dyn MetricsSink,
+) -> BlsScalar {
+    crate::hash::metered_compress(a, b, sink)
+}
+
+/// The same as [`hash_rows`](crate::hash_rows), reporting the workload to the
+/// sink.
+pub fn hash_rows<D, const N: usize>(
+    domain: D,
+    rows: &[[BlsScalar; N]],
+    sink: &dyn MetricsSink,
+) -> Vec<BlsScalar>
+where
+    D: DomainKind + Copy,
+{
+    crate::hash::metered_hash_rows(domain, rows, sink)
+}
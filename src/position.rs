@@ -0,0 +1,61 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#[cfg(feature = "zk")]
+pub(crate) mod gadget;
+
+use dusk_bls12_381::BlsScalar;
+
+use crate::{DomainKind, Hash};
+
+// Return the number of bits of a position in a tree of the given arity and
+// height.
+fn position_bits(arity: usize, height: usize) -> usize {
+    assert!(
+        arity > 1 && arity.is_power_of_two(),
+        "the arity needs to be a power of two"
+    );
+
+    let bits = arity.trailing_zeros() as usize * height;
+    assert!(
+        bits > 0 && bits <= 64,
+        "the positions of the tree need to fit into 1 to 64 bits"
+    );
+
+    bits
+}
+
+/// Derive the position of the leaf belonging to `key` in a tree of arity `A`
+/// and the given height.
+///
+/// The position is the lowest `log2(A) * height` bits of the truncated hash
+/// of the key, so that independent parties always map the same key to the
+/// same leaf of the tree. The same position can be computed in a circuit with
+/// [`position_of_gadget`](crate::position_of_gadget).
+///
+/// # Panics
+/// This function panics when the arity is not a power of two, when the
+/// positions of the tree don't fit into 1 to 64 bits, or when the domain
+/// rejects an input of one element.
+pub fn position_of<D: DomainKind, const A: usize>(
+    domain: D,
+    key: &BlsScalar,
+    height: usize,
+) -> u64 {
+    let bits = position_bits(A, height);
+
+    let hash = Hash::digest_truncated(domain, core::slice::from_ref(key));
+    let bytes = hash[0].to_bytes();
+
+    let mut position_bytes = [0u8; 8];
+    position_bytes.copy_from_slice(&bytes[..8]);
+    let position = u64::from_le_bytes(position_bytes);
+
+    match bits {
+        64 => position,
+        _ => position & ((1 << bits) - 1),
+    }
+}
@@ -0,0 +1,35 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_plonk::prelude::{Composer, Witness};
+
+use crate::gadgets::compose;
+use crate::hash::gadget::digest_truncated_bits;
+use crate::DomainKind;
+
+use super::position_bits;
+
+/// Derive the position of the leaf belonging to the `key` witness in a tree
+/// of arity `A` and the given height in the circuit, the same way as with
+/// [`position_of`](crate::position_of).
+///
+/// # Panics
+/// This function panics when the arity is not a power of two, when the
+/// positions of the tree don't fit into 1 to 64 bits, or when the domain
+/// rejects an input of one element.
+pub fn position_of_gadget<D: DomainKind, const A: usize>(
+    composer: &mut Composer,
+    domain: D,
+    key: Witness,
+    height: usize,
+) -> Witness {
+    let bits = position_bits(A, height);
+
+    let hash_bits =
+        digest_truncated_bits(composer, domain, core::slice::from_ref(&key));
+
+    compose(composer, &hash_bits[..bits], 1)
+}
@@ -0,0 +1,148 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! A probabilistic set, similar to a Bloom filter, keyed by the Poseidon
+//! hash.
+//!
+//! Every item is mapped to a number of indices of a bit-vector, derived from
+//! the hash of the item. A set never misses an inserted item, but may report
+//! an item as contained that has never been inserted, e.g. for cheap
+//! duplicate suppression where false positives are acceptable.
+//! The set can be committed to with its [`root`](PoseidonSet::root).
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use dusk_bls12_381::BlsScalar;
+
+use crate::hash::SET_SEPARATOR;
+use crate::{DomainKind, Error, Hash};
+
+/// The domains of the hashes of the set.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum SetDomain {
+    /// Deriving the indices of an item.
+    Index,
+    /// Committing to the bit-vector.
+    Root,
+}
+
+impl DomainKind for SetDomain {
+    /// The indices accept any output length, the root requires an output of
+    /// one element.
+    fn validate(
+        &self,
+        _input_len: usize,
+        output_len: usize,
+    ) -> Result<(), Error> {
+        match self {
+            SetDomain::Root if output_len != 1 => {
+                Err(Error::IOPatternViolation)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn tag_input(&self) -> u64 {
+        match self {
+            SetDomain::Index => SET_SEPARATOR,
+            SetDomain::Root => SET_SEPARATOR + 1,
+        }
+    }
+}
+
+/// The amount of 64-bit words that are packed into one scalar of the root.
+const WORDS_PER_SCALAR: usize = 3;
+
+/// A probabilistic set of items, each item being a slice of scalars.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoseidonSet {
+    words: Vec<u64>,
+    bits: usize,
+    hashes: usize,
+}
+
+impl PoseidonSet {
+    /// Create an empty set with a bit-vector of the given number of bits, and
+    /// the given number of indices per item.
+    ///
+    /// # Panics
+    /// This function panics when either the number of bits or the number of
+    /// hashes is zero.
+    pub fn new(bits: usize, hashes: usize) -> Self {
+        assert!(bits > 0, "the set needs at least one bit");
+        assert!(hashes > 0, "the set needs at least one hash");
+
+        Self {
+            words: vec![0; bits.div_ceil(64)],
+            bits,
+            hashes,
+        }
+    }
+
+    /// Return the number of bits of the bit-vector.
+    pub fn bits(&self) -> usize {
+        self.bits
+    }
+
+    /// Return the number of indices per item.
+    pub fn hashes(&self) -> usize {
+        self.hashes
+    }
+
+    /// Insert the item into the set.
+    pub fn insert(&mut self, item: &[BlsScalar]) {
+        self.indices(item)
+            .into_iter()
+            .for_each(|i| self.words[i / 64] |= 1 << (i % 64));
+    }
+
+    /// Return `false` if the item has certainly never been inserted into the
+    /// set, and `true` if it may have been.
+    pub fn maybe_contains(&self, item: &[BlsScalar]) -> bool {
+        self.indices(item)
+            .into_iter()
+            .all(|i| self.words[i / 64] & (1 << (i % 64)) != 0)
+    }
+
+    /// Return the commitment to the set, the hash of its parameters and its
+    /// bit-vector.
+    pub fn root(&self) -> BlsScalar {
+        let mut input =
+            Vec::with_capacity(self.words.len().div_ceil(WORDS_PER_SCALAR) + 2);
+        input.push(BlsScalar::from(self.bits as u64));
+        input.push(BlsScalar::from(self.hashes as u64));
+        input.extend(self.words.chunks(WORDS_PER_SCALAR).map(|chunk| {
+            let mut limbs = [0u64; 4];
+            limbs[..chunk.len()].copy_from_slice(chunk);
+            BlsScalar::from_raw(limbs)
+        }));
+
+        Hash::digest(SetDomain::Root, &input)[0]
+    }
+
+    // Derive the indices of the item from its hash, which also binds the
+    // parameters of the set.
+    fn indices(&self, item: &[BlsScalar]) -> Vec<usize> {
+        let params = [
+            BlsScalar::from(self.bits as u64),
+            BlsScalar::from(self.hashes as u64),
+        ];
+
+        let mut hash = Hash::new(SetDomain::Index);
+        hash.update(&params);
+        // an empty item is not absorbed, the sponge rejects absorbing nothing
+        if !item.is_empty() {
+            hash.update(item);
+        }
+        hash.output_len(self.hashes);
+
+        hash.finalize()
+            .iter()
+            .map(|s| (s.reduce().0[0] % self.bits as u64) as usize)
+            .collect()
+    }
+}
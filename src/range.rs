@@ -0,0 +1,73 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#[cfg(feature = "zk")]
+pub(crate) mod gadget;
+
+use alloc::vec::Vec;
+
+use dusk_bls12_381::BlsScalar;
+
+use crate::hash::{SeparatedDomain, RANGE_SEPARATOR};
+use crate::Hash;
+
+/// The amount of bits of each digit a value is decomposed into for its range
+/// commitment.
+pub const RANGE_DIGIT_BITS: usize = 8;
+
+/// The maximum amount of bits a value can be range-committed to, the value of
+/// any decomposition of that size is smaller than the scalar modulus.
+pub const MAX_RANGE_BITS: usize = 254;
+
+// Assert that the amount of bits is supported.
+fn check_bits(bits: usize) {
+    assert!(
+        bits > 0 && bits <= MAX_RANGE_BITS,
+        "the amount of bits needs to be between 1 and {MAX_RANGE_BITS}"
+    );
+}
+
+// Return the amount of digits a value of the given bits is decomposed into.
+fn digit_count(bits: usize) -> usize {
+    bits.div_ceil(RANGE_DIGIT_BITS)
+}
+
+/// Compute the commitment to a value being smaller than `2^bits`, hidden by
+/// the `blinder`.
+///
+/// The value is decomposed into little-endian digits of [`RANGE_DIGIT_BITS`]
+/// bits each and the commitment is the hash of the blinder followed by those
+/// digits in a dedicated domain. Without a random blinder, the commitment to
+/// a small value can be opened by hashing every value of the range.
+/// Returns `None` if the value doesn't fit into the given amount of bits.
+///
+/// # Panics
+/// This function panics when `bits` is zero or larger than
+/// [`MAX_RANGE_BITS`].
+pub fn range_commitment(
+    value: &BlsScalar,
+    blinder: &BlsScalar,
+    bits: usize,
+) -> Option<BlsScalar> {
+    check_bits(bits);
+
+    let bytes = value.to_bytes();
+    let fits = (bits..256).all(|i| (bytes[i / 8] >> (i % 8)) & 1 == 0);
+    if !fits {
+        return None;
+    }
+
+    // with digits of 8 bits, each digit is one byte of the value
+    let mut input = Vec::with_capacity(digit_count(bits) + 1);
+    input.push(*blinder);
+    input.extend(
+        bytes[..digit_count(bits)]
+            .iter()
+            .map(|byte| BlsScalar::from(*byte as u64)),
+    );
+
+    Some(Hash::digest(SeparatedDomain(RANGE_SEPARATOR), &input)[0])
+}
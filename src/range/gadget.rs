@@ -0,0 +1,55 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use alloc::vec::Vec;
+
+use dusk_plonk::prelude::{BlsScalar, Composer, Witness};
+
+use crate::gadgets::{compose, decompose};
+use crate::HashGadget;
+
+use super::{check_bits, RANGE_DIGIT_BITS};
+use crate::hash::{SeparatedDomain, RANGE_SEPARATOR};
+
+/// Assert that `value` is smaller than `2^bits` and that the hash of the
+/// `blinder` and its digits equals `commitment`.
+///
+/// The value is decomposed into boolean witnesses with
+/// [`decompose`](crate::gadgets::decompose) which are grouped into
+/// little-endian digits of [`RANGE_DIGIT_BITS`] bits. The hash of the blinder
+/// followed by the digits is asserted to equal the commitment, which is
+/// appended as public input.
+/// The commitment of a value is computed outside of the circuit with
+/// [`range_commitment`](crate::range_commitment).
+///
+/// # Panics
+/// This function panics when `bits` is zero or larger than
+/// [`MAX_RANGE_BITS`](crate::MAX_RANGE_BITS).
+pub fn assert_range_committed(
+    composer: &mut Composer,
+    value: Witness,
+    blinder: Witness,
+    bits: usize,
+    commitment: BlsScalar,
+) {
+    check_bits(bits);
+
+    let bits = decompose(composer, value, bits);
+
+    // the digits are bound to the value through the decomposed bits
+    let mut input =
+        Vec::with_capacity(bits.len().div_ceil(RANGE_DIGIT_BITS) + 1);
+    input.push(blinder);
+    input.extend(
+        bits.chunks(RANGE_DIGIT_BITS)
+            .map(|digit_bits| compose(composer, digit_bits, 1)),
+    );
+
+    let digest =
+        HashGadget::digest(composer, SeparatedDomain(RANGE_SEPARATOR), &input)
+            [0];
+    composer.assert_equal_constant(digest, 0, Some(commitment));
+}
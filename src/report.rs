@@ -0,0 +1,162 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Report of the amount of constraints each gadget of this crate appends to a
+//! circuit.
+//!
+//! The report allows downstream circuits to budget their constraints and to
+//! catch constraint regressions between releases.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use dusk_plonk::prelude::{Composer, Witness};
+
+use crate::{Domain, HashGadget, HADES_WIDTH};
+
+/// The input lengths for which the gadgets are compiled in [`report`].
+pub const REPORT_INPUT_LENGTHS: [usize; 6] = [1, 2, 4, 5, 8, 16];
+
+/// A gadget of this crate, with the length of its input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gadget {
+    /// [`HashGadget::digest`] with [`Domain::Other`].
+    Hash(usize),
+    /// [`HashGadget::digest_truncated`] with [`Domain::Other`].
+    HashTruncated(usize),
+    /// [`encrypt_gadget`](crate::encrypt_gadget) for a message of the given
+    /// length.
+    #[cfg(feature = "encryption")]
+    Encrypt(usize),
+    /// [`decrypt_gadget`](crate::decrypt_gadget) for a message of the given
+    /// length.
+    #[cfg(feature = "encryption")]
+    Decrypt(usize),
+}
+
+impl Gadget {
+    /// Compile the gadget into an empty circuit and return the amount of
+    /// constraints it appended.
+    ///
+    /// # Panics
+    /// This function panics when the input length is zero.
+    pub fn constraints(&self) -> usize {
+        let mut composer = Composer::initialized();
+
+        let input_len = match self {
+            Self::Hash(len) | Self::HashTruncated(len) => *len,
+            #[cfg(feature = "encryption")]
+            Self::Encrypt(len) => *len,
+            #[cfg(feature = "encryption")]
+            Self::Decrypt(len) => *len + 1,
+        };
+        let input: Vec<Witness> = (0..input_len)
+            .map(|i| composer.append_witness(i as u64))
+            .collect();
+
+        let initial = composer.constraints();
+        match self {
+            Self::Hash(_) => {
+                HashGadget::digest(&mut composer, Domain::Other, &input);
+            }
+            Self::HashTruncated(_) => {
+                HashGadget::digest_truncated(
+                    &mut composer,
+                    Domain::Other,
+                    &input,
+                );
+            }
+            #[cfg(feature = "encryption")]
+            Self::Encrypt(_) => {
+                crate::encrypt_gadget(
+                    &mut composer,
+                    &input,
+                    &Composer::IDENTITY,
+                    &Composer::ONE,
+                )
+                .expect("the message length should be valid");
+            }
+            #[cfg(feature = "encryption")]
+            Self::Decrypt(_) => {
+                crate::decrypt_gadget(
+                    &mut composer,
+                    &input,
+                    &Composer::IDENTITY,
+                    &Composer::ONE,
+                )
+                .expect("the cipher length should be valid");
+            }
+        }
+
+        composer.constraints() - initial
+    }
+}
+
+impl fmt::Display for Gadget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Hash(len) => write!(f, "hash {len} BlsScalar"),
+            Self::HashTruncated(len) => {
+                write!(f, "truncated hash {len} BlsScalar")
+            }
+            #[cfg(feature = "encryption")]
+            Self::Encrypt(len) => write!(f, "encrypt {len} BlsScalar"),
+            #[cfg(feature = "encryption")]
+            Self::Decrypt(len) => write!(f, "decrypt {len} BlsScalar"),
+        }
+    }
+}
+
+/// Compile every gadget of this crate for each of the
+/// [`REPORT_INPUT_LENGTHS`] and return the amount of constraints appended by
+/// each of them.
+pub fn report() -> Vec<(Gadget, usize)> {
+    let mut gadgets = Vec::new();
+    for len in REPORT_INPUT_LENGTHS {
+        gadgets.push(Gadget::Hash(len));
+        gadgets.push(Gadget::HashTruncated(len));
+        #[cfg(feature = "encryption")]
+        gadgets.push(Gadget::Encrypt(len));
+        #[cfg(feature = "encryption")]
+        gadgets.push(Gadget::Decrypt(len));
+    }
+
+    gadgets
+        .into_iter()
+        .map(|gadget| (gadget, gadget.constraints()))
+        .collect()
+}
+
+/// Compile a single permutation into an empty circuit and return the amount
+/// of constraints it appended.
+///
+/// The permutation is applied by the given closure, e.g. the
+/// [`permute`](crate::HadesCircuitBackend::permute) of a backend, which
+/// allows to compare alternative arithmetizations of the Hades permutation,
+/// e.g. using custom gates, against
+/// [`GadgetPermutation`](crate::GadgetPermutation). A backend is used for
+/// hashing with [`HashGadget::finalize_with`] and a
+/// [`BackendPermutation`](crate::BackendPermutation).
+///
+/// ```
+/// use dusk_poseidon::report::permutation_constraints;
+/// use dusk_poseidon::{GadgetBackend, HadesCircuitBackend};
+///
+/// let constraints = permutation_constraints(GadgetBackend::permute);
+/// assert!(constraints > 0);
+/// ```
+pub fn permutation_constraints(
+    permute: impl FnOnce(&mut Composer, &mut [Witness; HADES_WIDTH]),
+) -> usize {
+    let mut composer = Composer::initialized();
+    let mut state: [Witness; HADES_WIDTH] =
+        core::array::from_fn(|i| composer.append_witness(i as u64));
+
+    let initial = composer.constraints();
+    permute(&mut composer, &mut state);
+
+    composer.constraints() - initial
+}
@@ -0,0 +1,41 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bls12_381::BlsScalar;
+use dusk_poseidon::convert::packed_bytes_to_scalars;
+use dusk_poseidon::{bind_proof, Domain, Hash};
+
+#[test]
+fn bind_proof_deterministic() {
+    let proof = [0xab; 1040];
+
+    assert_eq!(bind_proof(&proof), bind_proof(&proof));
+    assert_ne!(bind_proof(&proof), bind_proof(&proof[1..]));
+}
+
+#[test]
+fn bind_proof_length() {
+    // trailing zero bytes pack into the same scalars, but the length is bound
+    let proof = [0xab; 30];
+    let mut padded = [0u8; 31];
+    padded[..30].copy_from_slice(&proof);
+
+    assert_eq!(
+        packed_bytes_to_scalars(&proof),
+        packed_bytes_to_scalars(&padded)
+    );
+    assert_ne!(bind_proof(&proof), bind_proof(&padded));
+}
+
+#[test]
+fn bind_proof_domain() {
+    let proof = [0xab; 64];
+
+    let mut input = vec![BlsScalar::from(proof.len() as u64)];
+    input.extend(packed_bytes_to_scalars(&proof));
+
+    assert_ne!(bind_proof(&proof), Hash::digest(Domain::Other, &input)[0]);
+}
@@ -0,0 +1,161 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bls12_381::BlsScalar;
+use dusk_bytes::ParseHexStr;
+use dusk_poseidon::compat::sponge;
+use dusk_poseidon::{Domain, Hash};
+
+const TEST_INPUTS: [&str; 10] = [
+    "bb67ed265bf1db490ded2e1ede55c0d14c55521509dc73f9c354e98ab76c9625",
+    "7e74220084d75e10c89e9435d47bb5b8075991b2e29be3b84421dac3b1ee6007",
+    "5ce5481a4d78cca03498f72761da1b9f1d2aa8fb300be39f0e4fe2534f9d4308",
+    "b1e710e3c4a8c35154b0ce4e4f4af6f498ebd79f8e7cdf3150372c7501be250b",
+    "33c9e2025f86b5d82149f1ab8e20a168fc3d99d09b48cbce0286db8752cc3306",
+    "e98206bfdce791e4e5144079b997d4fc25006194b35655f0e48490b26e24ea35",
+    "86d2a95cc552de8d5bb20bd4a407fee5ffdc314e93dfe6b2dc792bc71fd8cc2d",
+    "4edd8307ce28a8c70963d20a7bc28df1e1720bbbc93878a18bd07fad7d51fa15",
+    "eabc7a296704a68aa01f95adc85f6dd758b175745336d8fc795a17984024b21e",
+    "cfc108673c93df305e31c283b9c767b7097ae4e174a223e0c24b15a67b701a3a",
+];
+
+fn test_inputs() -> Vec<BlsScalar> {
+    TEST_INPUTS
+        .iter()
+        .map(|input| BlsScalar::from_hex_str(input).unwrap())
+        .collect()
+}
+
+// the expected outputs are taken from the tests of version 0.33.0
+#[test]
+fn legacy_sponge_hash() {
+    let test_inputs = test_inputs();
+
+    assert_eq!(
+        "0x2885ca6d908b34ca83f2177d78283c25d8c5c7230877025bc8d558b8a94e6fe3",
+        format!("{:?}", sponge::hash(&test_inputs[..3]))
+    );
+
+    assert_eq!(
+        "0x55f7f755570a884cb1430bf5cba11fff94430bea63f3c108e6070dc86532ea75",
+        format!("{:?}", sponge::hash(&test_inputs[..4]))
+    );
+
+    assert_eq!(
+        "0x4288f8b92a9a8bc20f60aac68a318b3287ddb0a663cef5015bff0e98a0063153",
+        format!("{:?}", sponge::hash(&test_inputs[..5]))
+    );
+
+    assert_eq!(
+        "0x31673ed327fa548518084e7332c29d4c96ad314cee79d2c447ff4f686458811a",
+        format!("{:?}", sponge::hash(&test_inputs[..6]))
+    );
+
+    assert_eq!(
+        "0x5aa2df13cf6f910c19e5516c9222df7039d119472534e6488081df57d036b9a8",
+        format!("{:?}", sponge::hash(&test_inputs[..8]))
+    );
+
+    assert_eq!(
+        "0x0be3889073101db27cd27006256daedda56368a4dad19e6fc810041a23342998",
+        format!("{:?}", sponge::hash(&test_inputs[..10]))
+    );
+
+    assert_eq!(sponge::hash(&[]), BlsScalar::zero());
+}
+
+#[test]
+fn legacy_differs_from_hash() {
+    let test_inputs = test_inputs();
+
+    assert_ne!(
+        sponge::hash(&test_inputs),
+        Hash::digest(Domain::Other, &test_inputs)[0]
+    );
+}
+
+#[cfg(feature = "zk")]
+mod gadget {
+    use super::*;
+
+    use dusk_plonk::prelude::Error as PlonkError;
+    use dusk_plonk::prelude::*;
+    use once_cell::sync::Lazy;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    static PUB_PARAMS: Lazy<PublicParameters> = Lazy::new(|| {
+        let mut rng = StdRng::seed_from_u64(0xbeef);
+
+        const CAPACITY: usize = 12;
+        PublicParameters::setup(1 << CAPACITY, &mut rng)
+            .expect("Setup of public params should pass")
+    });
+
+    // the circuit hashes the input with the truncated gadget if `T` is set
+    #[derive(Debug)]
+    struct LegacyCircuit<const L: usize, const T: bool> {
+        input: [BlsScalar; L],
+    }
+
+    impl<const L: usize, const T: bool> Default for LegacyCircuit<L, T> {
+        fn default() -> Self {
+            Self {
+                input: [BlsScalar::zero(); L],
+            }
+        }
+    }
+
+    impl<const L: usize, const T: bool> Circuit for LegacyCircuit<L, T> {
+        fn circuit(&self, composer: &mut Composer) -> Result<(), PlonkError> {
+            let input = self.input.map(|i| composer.append_witness(i));
+
+            let (output, expected) = match T {
+                false => (
+                    sponge::gadget(composer, &input),
+                    sponge::hash(&self.input),
+                ),
+                true => (
+                    sponge::truncated::gadget(composer, &input),
+                    sponge::truncated::hash(&self.input).into(),
+                ),
+            };
+            composer.assert_equal_constant(output, 0, Some(expected));
+
+            Ok(())
+        }
+    }
+
+    fn prove_and_verify<const L: usize, const T: bool>(
+    ) -> Result<(), PlonkError> {
+        let mut rng = StdRng::seed_from_u64(0xbeef);
+
+        let label = b"legacy-sponge-tester";
+        let (prover, verifier) =
+            Compiler::compile::<LegacyCircuit<L, T>>(&PUB_PARAMS, label)?;
+
+        let circuit = LegacyCircuit::<L, T> {
+            input: test_inputs()[..L].try_into().unwrap(),
+        };
+        let (proof, public_inputs) = prover.prove(&mut rng, &circuit)?;
+
+        verifier.verify(&proof, &public_inputs)
+    }
+
+    #[test]
+    fn legacy_sponge_gadget() -> Result<(), PlonkError> {
+        prove_and_verify::<0, false>()?;
+        prove_and_verify::<3, false>()?;
+        prove_and_verify::<4, false>()
+    }
+
+    #[test]
+    fn legacy_sponge_truncated_gadget() -> Result<(), PlonkError> {
+        prove_and_verify::<0, true>()?;
+        prove_and_verify::<3, true>()?;
+        prove_and_verify::<4, true>()
+    }
+}
@@ -0,0 +1,70 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bls12_381::BlsScalar;
+use dusk_poseidon::convert::{
+    bytes_to_scalars, packed_bytes_to_scalars, u64s_to_scalars,
+    wide_bytes_to_scalars, PACKED_BYTES,
+};
+use ff::Field;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn u64s() {
+    let values = [0, 1, 42, u64::MAX];
+    let scalars = u64s_to_scalars(&values);
+
+    assert_eq!(scalars.len(), values.len());
+    values
+        .iter()
+        .zip(scalars.iter())
+        .for_each(|(v, s)| assert_eq!(BlsScalar::from(*v), *s));
+}
+
+#[test]
+fn bytes() {
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+    let scalars: Vec<BlsScalar> =
+        (0..5).map(|_| BlsScalar::random(&mut rng)).collect();
+    let bytes: Vec<u8> = scalars.iter().flat_map(|s| s.to_bytes()).collect();
+
+    assert_eq!(bytes_to_scalars(&bytes), Some(scalars));
+    assert_eq!(bytes_to_scalars(&[]), Some(vec![]));
+
+    // wrong length and non-canonical encodings are rejected
+    assert_eq!(bytes_to_scalars(&bytes[1..]), None);
+    assert_eq!(bytes_to_scalars(&[0xff; 64]), None);
+}
+
+#[test]
+fn wide_bytes() {
+    let bytes = [0xffu8; 128];
+    let scalars = wide_bytes_to_scalars(&bytes).expect("length is valid");
+
+    assert_eq!(scalars, vec![BlsScalar::from_bytes_wide(&[0xff; 64]); 2]);
+    assert_eq!(wide_bytes_to_scalars(&bytes[..96]), None);
+}
+
+#[test]
+fn packed_bytes() {
+    let bytes = [0xffu8; 2 * PACKED_BYTES + 1];
+    let scalars = packed_bytes_to_scalars(&bytes);
+
+    let mut full = [0xffu8; 32];
+    full[PACKED_BYTES] = 0;
+    let mut last = [0u8; 32];
+    last[0] = 0xff;
+    assert_eq!(
+        scalars,
+        vec![
+            BlsScalar::from_bytes(&full).unwrap(),
+            BlsScalar::from_bytes(&full).unwrap(),
+            BlsScalar::from_bytes(&last).unwrap(),
+        ]
+    );
+    assert!(packed_bytes_to_scalars(&[]).is_empty());
+}
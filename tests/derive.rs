@@ -0,0 +1,133 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bls12_381::BlsScalar;
+use dusk_poseidon::{derive_child, is_reserved_encoding, Domain, Hash};
+
+const WALLET_DOMAIN: u64 = 0x7761_6c6c_6574;
+
+#[test]
+fn derive_child_encoding() {
+    let parent = BlsScalar::from(42);
+
+    // the child is hashed in its own domain
+    assert_ne!(
+        derive_child(parent, 1, WALLET_DOMAIN),
+        Hash::digest(
+            Domain::Other,
+            &[BlsScalar::from(WALLET_DOMAIN), parent, BlsScalar::one()]
+        )[0]
+    );
+    assert!(is_reserved_encoding(1 << 38));
+
+    // the index, the parent and the domain all change the child
+    let child = derive_child(parent, 1, WALLET_DOMAIN);
+    assert_ne!(child, derive_child(parent, 2, WALLET_DOMAIN));
+    assert_ne!(child, derive_child(parent, 1 + (1 << 63), WALLET_DOMAIN));
+    assert_ne!(child, derive_child(BlsScalar::from(43), 1, WALLET_DOMAIN));
+    assert_ne!(child, derive_child(parent, 1, WALLET_DOMAIN + 1));
+}
+
+#[cfg(feature = "encryption")]
+#[test]
+fn derivations_dont_collide() {
+    use dusk_poseidon::{derive_key, Nonce};
+
+    // truncate the scalar to 250 bits, the same way as the derived keys
+    fn truncated(scalar: &BlsScalar) -> [u8; 32] {
+        let mut bytes = scalar.to_bytes();
+        bytes[31] &= 0x03;
+        bytes
+    }
+
+    let parent = BlsScalar::from(42);
+    let index = BlsScalar::one();
+
+    // all three derivations hash [domain, parent, index] for these inputs,
+    // with the length of the empty key material as domain of the key
+    let child = derive_child(parent, 1, WALLET_DOMAIN);
+    let nonce = Nonce::derive(WALLET_DOMAIN, &[parent, index]);
+    assert_ne!(child, *nonce.as_scalar());
+
+    let key = derive_key(&[], &[parent, index]);
+    let nonce = Nonce::derive(0, &[parent, index]);
+    let child = derive_child(parent, 1, 0);
+    assert_ne!(key.to_bytes(), truncated(nonce.as_scalar()));
+    assert_ne!(key.to_bytes(), truncated(&child));
+}
+
+#[cfg(feature = "zk")]
+mod gadget {
+    use super::*;
+
+    use dusk_plonk::prelude::Error as PlonkError;
+    use dusk_plonk::prelude::*;
+    use dusk_poseidon::derive_child_gadget;
+    use once_cell::sync::Lazy;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    static PUB_PARAMS: Lazy<PublicParameters> = Lazy::new(|| {
+        let mut rng = StdRng::seed_from_u64(0xbeef);
+
+        const CAPACITY: usize = 11;
+        PublicParameters::setup(1 << CAPACITY, &mut rng)
+            .expect("Setup of public params should pass")
+    });
+
+    #[derive(Debug, Default)]
+    struct ChildCircuit {
+        parent: BlsScalar,
+        index: BlsScalar,
+        child: BlsScalar,
+    }
+
+    impl Circuit for ChildCircuit {
+        fn circuit(&self, composer: &mut Composer) -> Result<(), PlonkError> {
+            let parent = composer.append_witness(self.parent);
+            let index = composer.append_witness(self.index);
+
+            let child =
+                derive_child_gadget(composer, parent, index, WALLET_DOMAIN);
+            composer.assert_equal_constant(child, 0, Some(self.child));
+
+            Ok(())
+        }
+    }
+
+    fn prove_and_verify(circuit: &ChildCircuit) -> Result<(), PlonkError> {
+        let mut rng = StdRng::seed_from_u64(0xbeef);
+
+        let label = b"derive-tester";
+        let (prover, verifier) =
+            Compiler::compile::<ChildCircuit>(&PUB_PARAMS, label)?;
+
+        let (proof, public_inputs) = prover.prove(&mut rng, circuit)?;
+        verifier.verify(&proof, &public_inputs)
+    }
+
+    #[test]
+    fn derive_child_gadget_matches() -> Result<(), PlonkError> {
+        let parent = BlsScalar::from(42);
+        let index = (1 << 63) + 7;
+
+        let circuit = ChildCircuit {
+            parent,
+            index: BlsScalar::from(index),
+            child: derive_child(parent, index, WALLET_DOMAIN),
+        };
+        prove_and_verify(&circuit)?;
+
+        // a different index fails
+        let circuit = ChildCircuit {
+            index: BlsScalar::from(index + 1),
+            ..circuit
+        };
+        assert!(prove_and_verify(&circuit).is_err());
+
+        Ok(())
+    }
+}
@@ -8,7 +8,7 @@
 
 use dusk_bls12_381::BlsScalar;
 use dusk_jubjub::{JubJubAffine, JubJubScalar, GENERATOR_EXTENDED};
-use dusk_poseidon::{decrypt, encrypt, Error};
+use dusk_poseidon::{decrypt, derive_key, encrypt, reencrypt, Error, Nonce};
 use ff::Field;
 use rand::rngs::StdRng;
 use rand::SeedableRng;
@@ -16,13 +16,13 @@ use rand::SeedableRng;
 fn encryption_variables(
     rng: &mut StdRng,
     message_len: usize,
-) -> (Vec<BlsScalar>, JubJubAffine, BlsScalar) {
+) -> (Vec<BlsScalar>, JubJubAffine, Nonce) {
     let mut message = Vec::with_capacity(message_len);
     for _ in 0..message_len {
         message.push(BlsScalar::random(&mut *rng));
     }
-    let shared_secret = GENERATOR_EXTENDED * &JubJubScalar::random(&mut *rng);
-    let nonce = BlsScalar::random(&mut *rng);
+    let shared_secret = GENERATOR_EXTENDED * JubJubScalar::random(&mut *rng);
+    let nonce = Nonce::random(&mut *rng);
 
     (message, shared_secret.into(), nonce)
 }
@@ -35,9 +35,9 @@ fn encrypt_decrypt() -> Result<(), Error> {
     let (message, shared_secret, nonce) =
         encryption_variables(&mut rng, message_len);
 
-    let cipher = encrypt(&message, &shared_secret, &nonce)?;
+    let cipher = encrypt(&message, shared_secret, &nonce)?;
 
-    let decrypted_message = decrypt(&cipher, &shared_secret, &nonce)?;
+    let decrypted_message = decrypt(cipher, shared_secret, &nonce)?;
 
     assert_eq!(decrypted_message, message);
 
@@ -52,14 +52,14 @@ fn incorrect_shared_secret_fails() -> Result<(), Error> {
     let (message, shared_secret, nonce) =
         encryption_variables(&mut rng, message_len);
 
-    let cipher = encrypt(&message, &shared_secret, &nonce)?;
+    let cipher = encrypt(message, shared_secret, &nonce)?;
 
     let wrong_shared_secret =
-        GENERATOR_EXTENDED * &JubJubScalar::random(&mut rng);
+        GENERATOR_EXTENDED * JubJubScalar::random(&mut rng);
     assert_ne!(shared_secret, wrong_shared_secret.into());
 
     assert_eq!(
-        decrypt(&cipher, &wrong_shared_secret.into(), &nonce,).unwrap_err(),
+        decrypt(cipher, wrong_shared_secret, &nonce,).unwrap_err(),
         Error::DecryptionFailed
     );
 
@@ -74,13 +74,13 @@ fn incorrect_nonce_fails() -> Result<(), Error> {
     let (message, shared_secret, nonce) =
         encryption_variables(&mut rng, message_len);
 
-    let cipher = encrypt(&message, &shared_secret, &nonce)?;
+    let cipher = encrypt(message, shared_secret, &nonce)?;
 
-    let wrong_nonce = BlsScalar::random(&mut rng);
+    let wrong_nonce = Nonce::random(&mut rng);
     assert_ne!(nonce, wrong_nonce);
 
     assert_eq!(
-        decrypt(&cipher, &shared_secret, &wrong_nonce,).unwrap_err(),
+        decrypt(cipher, shared_secret, &wrong_nonce,).unwrap_err(),
         Error::DecryptionFailed
     );
 
@@ -95,21 +95,125 @@ fn incorrect_cipher_fails() -> Result<(), Error> {
     let (message, shared_secret, nonce) =
         encryption_variables(&mut rng, message_len);
 
-    let cipher = encrypt(&message, &shared_secret, &nonce)?;
+    let cipher = encrypt(message, shared_secret, &nonce)?;
 
     let mut wrong_cipher = cipher.clone();
     wrong_cipher[message_len] += BlsScalar::from(42);
     assert_eq!(
-        decrypt(&wrong_cipher, &shared_secret, &nonce,).unwrap_err(),
+        decrypt(&wrong_cipher, shared_secret, &nonce,).unwrap_err(),
         Error::DecryptionFailed
     );
 
     let mut wrong_cipher = cipher.clone();
     wrong_cipher[0] += BlsScalar::from(42);
     assert_eq!(
-        decrypt(&wrong_cipher, &shared_secret, &nonce,).unwrap_err(),
+        decrypt(&wrong_cipher, shared_secret, &nonce,).unwrap_err(),
         Error::DecryptionFailed
     );
 
     Ok(())
 }
+
+#[test]
+fn nonce_serialization() {
+    let mut rng = StdRng::seed_from_u64(0x42424242);
+    let nonce = Nonce::random(&mut rng);
+
+    assert_eq!(Nonce::from_bytes(&nonce.to_bytes()), Some(nonce));
+    assert_eq!(Nonce::from_bytes(&[0xff; Nonce::SIZE]), None);
+}
+
+#[test]
+fn nonce_from_counter() {
+    let domain = 42;
+
+    assert_eq!(
+        Nonce::from_counter(domain, 1),
+        Nonce::from_counter(domain, 1)
+    );
+    assert_ne!(
+        Nonce::from_counter(domain, 1),
+        Nonce::from_counter(domain, 2)
+    );
+    assert_ne!(
+        Nonce::from_counter(domain, 1),
+        Nonce::from_counter(domain + 1, 1)
+    );
+}
+
+#[test]
+fn nonce_derive() {
+    let domain = 42;
+    let context = [BlsScalar::from(1), BlsScalar::from(2)];
+
+    assert_eq!(
+        Nonce::derive(domain, &context),
+        Nonce::derive(domain, &context)
+    );
+    assert_ne!(
+        Nonce::derive(domain, &context),
+        Nonce::derive(domain, &context[..1])
+    );
+    assert_ne!(
+        Nonce::derive(domain, &context),
+        Nonce::derive(domain + 1, &context)
+    );
+    assert_eq!(
+        Nonce::derive(domain, &[BlsScalar::from(7)]),
+        Nonce::from_counter(domain, 7)
+    );
+}
+
+#[test]
+fn key_derive() {
+    let ikm = [BlsScalar::from(1), BlsScalar::from(2)];
+    let info = [BlsScalar::from(3)];
+
+    assert_eq!(derive_key(&ikm, &info), derive_key(&ikm, &info));
+    assert_ne!(derive_key(&ikm, &info), derive_key(&ikm, &[]));
+
+    // the boundary between key material and info can't be shifted
+    assert_ne!(
+        derive_key(&ikm, &info),
+        derive_key(&ikm[..1], &[ikm[1], info[0]])
+    );
+}
+
+#[test]
+fn reencrypt_rotates_keys() -> Result<(), Error> {
+    let mut rng = StdRng::seed_from_u64(0x42424242);
+
+    let (message, old_shared_secret, old_nonce) =
+        encryption_variables(&mut rng, 5);
+    let (_, new_shared_secret, new_nonce) = encryption_variables(&mut rng, 0);
+
+    let cipher = encrypt(&message, old_shared_secret, &old_nonce)?;
+    let new_cipher = reencrypt(
+        &cipher,
+        old_shared_secret,
+        &old_nonce,
+        new_shared_secret,
+        &new_nonce,
+    )?;
+
+    assert_eq!(new_cipher.len(), cipher.len());
+    assert_eq!(
+        decrypt(&new_cipher, new_shared_secret, &new_nonce)?,
+        message
+    );
+    assert!(decrypt(&new_cipher, old_shared_secret, &old_nonce).is_err());
+
+    // re-encryption fails with the wrong old secret
+    assert_eq!(
+        reencrypt(
+            &cipher,
+            new_shared_secret,
+            &old_nonce,
+            new_shared_secret,
+            &new_nonce,
+        ),
+        Err(Error::DecryptionFailed)
+    );
+
+    Ok(())
+}
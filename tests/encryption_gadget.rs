@@ -11,7 +11,12 @@ use dusk_bls12_381::BlsScalar;
 use dusk_jubjub::{JubJubAffine, JubJubScalar, GENERATOR_EXTENDED};
 use dusk_plonk::prelude::Error as PlonkError;
 use dusk_plonk::prelude::*;
-use dusk_poseidon::{decrypt_gadget, encrypt, encrypt_gadget};
+use dusk_poseidon::{
+    assert_nonce_derived, decrypt_gadget, derive_key, derive_key_gadget,
+    encrypt, encrypt_gadget, encrypt_gadget_pi, initial_state,
+    initial_state_gadget, reencrypt_gadget, verify_cipher_gadget, Nonce,
+    HADES_WIDTH,
+};
 use ff::Field;
 use once_cell::sync::Lazy;
 use rand::rngs::StdRng;
@@ -41,9 +46,9 @@ impl<const L: usize> EncryptionCircuit<L> {
             .iter_mut()
             .for_each(|s| *s = BlsScalar::random(&mut *rng));
         let shared_secret =
-            GENERATOR_EXTENDED * &JubJubScalar::random(&mut *rng);
+            GENERATOR_EXTENDED * JubJubScalar::random(&mut *rng);
         let nonce = BlsScalar::random(&mut *rng);
-        let cipher = encrypt(&message, &shared_secret.into(), &nonce)
+        let cipher = encrypt(message, shared_secret, &nonce.into())
             .expect("encryption should pass");
         assert_eq!(message.len() + 1, cipher.len());
 
@@ -86,7 +91,7 @@ impl<const L: usize> Circuit for EncryptionCircuit<L> {
 
         // encrypt the message with the gadget
         let cipher_result =
-            encrypt_gadget(composer, &message_wit, &secret_wit, &nonce_wit)
+            encrypt_gadget(composer, message_wit, &secret_wit, &nonce_wit)
                 .expect("encryption should pass");
 
         // ensure that the resulting cipher-text is correct
@@ -144,7 +149,7 @@ fn incorrect_shared_secret_fails() -> Result<(), Error> {
         EncryptionCircuit::random(&mut rng);
 
     let wrong_shared_secret =
-        GENERATOR_EXTENDED * &JubJubScalar::random(&mut rng);
+        GENERATOR_EXTENDED * JubJubScalar::random(&mut rng);
     circuit.shared_secret = wrong_shared_secret.into();
 
     assert!(prover.prove(&mut rng, &circuit).is_err());
@@ -215,3 +220,366 @@ fn incorrect_public_input_fails() -> Result<(), Error> {
 
     Ok(())
 }
+
+#[derive(Debug, Default)]
+struct EncryptionPiCircuit<const L: usize> {
+    circuit: EncryptionCircuit<L>,
+}
+
+impl<const L: usize> Circuit for EncryptionPiCircuit<L> {
+    fn circuit(&self, composer: &mut Composer) -> Result<(), PlonkError> {
+        // append all variables to the circuit
+        let mut message_wit = [Composer::ZERO; L];
+        message_wit
+            .iter_mut()
+            .zip(self.circuit.message)
+            .for_each(|(w, m)| *w = composer.append_witness(m));
+        let secret_wit = composer.append_point(self.circuit.shared_secret);
+        let nonce_wit = composer.append_witness(self.circuit.nonce);
+
+        // encrypt the message and append the cipher as public inputs
+        let cipher_result =
+            encrypt_gadget_pi(composer, message_wit, &secret_wit, &nonce_wit)
+                .expect("encryption should pass");
+        assert_eq!(cipher_result.len(), L + 1);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn encrypt_public_inputs() -> Result<(), PlonkError> {
+    let mut rng = StdRng::seed_from_u64(0x42424242);
+    const MESSAGE_LEN: usize = 3;
+
+    let (prover, verifier) = Compiler::compile::<
+        EncryptionPiCircuit<MESSAGE_LEN>,
+    >(&PUB_PARAMS, LABEL)?;
+
+    let circuit = EncryptionPiCircuit {
+        circuit: EncryptionCircuit::<MESSAGE_LEN>::random(&mut rng),
+    };
+
+    let (proof, public_inputs) = prover.prove(&mut rng, &circuit)?;
+    assert_eq!(public_inputs, circuit.circuit.cipher);
+
+    verifier.verify(&proof, &circuit.circuit.cipher)?;
+
+    let mut wrong_cipher = circuit.circuit.cipher.clone();
+    wrong_cipher[0] = BlsScalar::random(&mut rng);
+    assert!(verifier.verify(&proof, &wrong_cipher).is_err());
+
+    Ok(())
+}
+
+const NONCE_DOMAIN: u64 = 42;
+
+#[derive(Debug, Default)]
+struct NonceCircuit {
+    context: [BlsScalar; 3],
+    nonce: BlsScalar,
+}
+
+impl Circuit for NonceCircuit {
+    fn circuit(&self, composer: &mut Composer) -> Result<(), PlonkError> {
+        let context_wit = self.context.map(|c| composer.append_witness(c));
+        let nonce_wit = composer.append_witness(self.nonce);
+
+        assert_nonce_derived(composer, &nonce_wit, NONCE_DOMAIN, &context_wit);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn nonce_derived() -> Result<(), PlonkError> {
+    let mut rng = StdRng::seed_from_u64(0x42424242);
+
+    let (prover, verifier) =
+        Compiler::compile::<NonceCircuit>(&PUB_PARAMS, LABEL)?;
+
+    let context = [
+        BlsScalar::random(&mut rng),
+        BlsScalar::random(&mut rng),
+        BlsScalar::random(&mut rng),
+    ];
+    let nonce = Nonce::derive(NONCE_DOMAIN, &context).into();
+    let circuit = NonceCircuit { context, nonce };
+
+    let (proof, public_inputs) = prover.prove(&mut rng, &circuit)?;
+    verifier.verify(&proof, &public_inputs)?;
+
+    // a nonce derived from a different domain fails
+    let nonce = Nonce::derive(NONCE_DOMAIN + 1, &context).into();
+    let circuit = NonceCircuit { context, nonce };
+    assert!(prover.prove(&mut rng, &circuit).is_err());
+
+    Ok(())
+}
+
+const KEY_INFO: [BlsScalar; 2] = [BlsScalar::one(), BlsScalar::one()];
+
+#[derive(Debug, Default)]
+struct KeyCircuit {
+    ikm: [BlsScalar; 2],
+    public_key: JubJubAffine,
+}
+
+impl Circuit for KeyCircuit {
+    fn circuit(&self, composer: &mut Composer) -> Result<(), PlonkError> {
+        let ikm_wit = self.ikm.map(|i| composer.append_witness(i));
+
+        let key = derive_key_gadget(composer, &ikm_wit, &KEY_INFO);
+        let public_key =
+            composer.component_mul_generator(key, GENERATOR_EXTENDED)?;
+        composer.assert_equal_public_point(public_key, self.public_key);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn key_derived() -> Result<(), PlonkError> {
+    let mut rng = StdRng::seed_from_u64(0x42424242);
+
+    let (prover, verifier) =
+        Compiler::compile::<KeyCircuit>(&PUB_PARAMS, LABEL)?;
+
+    let ikm = [BlsScalar::random(&mut rng), BlsScalar::random(&mut rng)];
+    let public_key = (GENERATOR_EXTENDED * derive_key(&ikm, &KEY_INFO)).into();
+    let circuit = KeyCircuit { ikm, public_key };
+
+    let (proof, public_inputs) = prover.prove(&mut rng, &circuit)?;
+    verifier.verify(&proof, &public_inputs)?;
+
+    // a key derived with different info fails
+    let public_key = (GENERATOR_EXTENDED * derive_key(&ikm, &[])).into();
+    let circuit = KeyCircuit { ikm, public_key };
+    assert!(prover.prove(&mut rng, &circuit).is_err());
+
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+struct InitialStateCircuit {
+    shared_secret: JubJubAffine,
+    nonce: BlsScalar,
+    state: [BlsScalar; HADES_WIDTH],
+}
+
+impl Circuit for InitialStateCircuit {
+    fn circuit(&self, composer: &mut Composer) -> Result<(), PlonkError> {
+        let shared_secret = composer.append_point(self.shared_secret);
+        let nonce = composer.append_witness(self.nonce);
+
+        let state = initial_state_gadget(composer, &shared_secret, &nonce, 2)
+            .expect("the message length is valid");
+        state.iter().zip(self.state.iter()).for_each(|(w, s)| {
+            composer.assert_equal_constant(*w, 0, Some(*s));
+        });
+
+        Ok(())
+    }
+}
+
+#[test]
+fn initial_state_gadget_matches() -> Result<(), PlonkError> {
+    let mut rng = StdRng::seed_from_u64(0x42424242);
+
+    let (prover, verifier) =
+        Compiler::compile::<InitialStateCircuit>(&PUB_PARAMS, LABEL)?;
+
+    let shared_secret: JubJubAffine =
+        (GENERATOR_EXTENDED * JubJubScalar::random(&mut rng)).into();
+    let nonce = BlsScalar::random(&mut rng);
+    let state = initial_state(shared_secret, &nonce.into(), 2)
+        .expect("the message length is valid");
+    let circuit = InitialStateCircuit {
+        shared_secret,
+        nonce,
+        state,
+    };
+
+    let (proof, public_inputs) = prover.prove(&mut rng, &circuit)?;
+    verifier.verify(&proof, &public_inputs)?;
+    assert_eq!(public_inputs, state);
+
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+struct ReencryptionCircuit {
+    cipher: [BlsScalar; 3],
+    old_shared_secret: JubJubAffine,
+    old_nonce: BlsScalar,
+    new_shared_secret: JubJubAffine,
+    new_nonce: BlsScalar,
+    new_cipher: [BlsScalar; 3],
+}
+
+impl Circuit for ReencryptionCircuit {
+    fn circuit(&self, composer: &mut Composer) -> Result<(), PlonkError> {
+        let cipher = self.cipher.map(|c| composer.append_witness(c));
+        let old_shared_secret = composer.append_point(self.old_shared_secret);
+        let old_nonce = composer.append_witness(self.old_nonce);
+        let new_shared_secret = composer.append_point(self.new_shared_secret);
+        let new_nonce = composer.append_witness(self.new_nonce);
+
+        let new_cipher = reencrypt_gadget(
+            composer,
+            cipher,
+            &old_shared_secret,
+            &old_nonce,
+            &new_shared_secret,
+            &new_nonce,
+        )
+        .expect("re-encryption should pass");
+        new_cipher
+            .iter()
+            .zip(self.new_cipher.iter())
+            .for_each(|(w, c)| {
+                composer.assert_equal_constant(*w, 0, Some(*c));
+            });
+
+        Ok(())
+    }
+}
+
+#[test]
+fn reencryption() -> Result<(), PlonkError> {
+    let mut rng = StdRng::seed_from_u64(0x42424242);
+
+    let (prover, verifier) =
+        Compiler::compile::<ReencryptionCircuit>(&PUB_PARAMS, LABEL)?;
+
+    let message = [BlsScalar::random(&mut rng), BlsScalar::random(&mut rng)];
+    let old_shared_secret: JubJubAffine =
+        (GENERATOR_EXTENDED * JubJubScalar::random(&mut rng)).into();
+    let old_nonce = BlsScalar::random(&mut rng);
+    let new_shared_secret: JubJubAffine =
+        (GENERATOR_EXTENDED * JubJubScalar::random(&mut rng)).into();
+    let new_nonce = BlsScalar::random(&mut rng);
+
+    let cipher = encrypt(message, old_shared_secret, &old_nonce.into())
+        .expect("encryption should pass");
+    let new_cipher = encrypt(message, new_shared_secret, &new_nonce.into())
+        .expect("encryption should pass");
+
+    let mut circuit = ReencryptionCircuit {
+        cipher: [cipher[0], cipher[1], cipher[2]],
+        old_shared_secret,
+        old_nonce,
+        new_shared_secret,
+        new_nonce,
+        new_cipher: [new_cipher[0], new_cipher[1], new_cipher[2]],
+    };
+
+    let (proof, public_inputs) = prover.prove(&mut rng, &circuit)?;
+    verifier.verify(&proof, &public_inputs)?;
+    assert_eq!(public_inputs, new_cipher);
+
+    // a cipher that doesn't decrypt with the old secret fails
+    circuit.old_nonce = new_nonce;
+    assert!(prover.prove(&mut rng, &circuit).is_err());
+
+    Ok(())
+}
+
+#[derive(Debug)]
+struct VerifyCipherCircuit<const L: usize> {
+    cipher: Vec<BlsScalar>,
+    shared_secret: JubJubAffine,
+    nonce: BlsScalar,
+}
+
+impl<const L: usize> Default for VerifyCipherCircuit<L> {
+    fn default() -> Self {
+        Self {
+            cipher: vec![BlsScalar::zero(); L + 1],
+            shared_secret: JubJubAffine::identity(),
+            nonce: BlsScalar::zero(),
+        }
+    }
+}
+
+impl<const L: usize> Circuit for VerifyCipherCircuit<L> {
+    fn circuit(&self, composer: &mut Composer) -> Result<(), PlonkError> {
+        let cipher: Vec<Witness> = self
+            .cipher
+            .iter()
+            .map(|c| composer.append_witness(*c))
+            .collect();
+        let shared_secret = composer.append_point(self.shared_secret);
+        let nonce = composer.append_witness(self.nonce);
+
+        verify_cipher_gadget(composer, cipher, &shared_secret, &nonce)
+            .expect("the cipher length is valid");
+
+        Ok(())
+    }
+}
+
+fn verify_cipher<const L: usize>(rng: &mut StdRng) -> Result<(), PlonkError> {
+    let (prover, verifier) =
+        Compiler::compile::<VerifyCipherCircuit<L>>(&PUB_PARAMS, LABEL)?;
+
+    let message: Vec<BlsScalar> =
+        (0..L).map(|_| BlsScalar::random(&mut *rng)).collect();
+    let shared_secret: JubJubAffine =
+        (GENERATOR_EXTENDED * JubJubScalar::random(&mut *rng)).into();
+    let nonce = BlsScalar::random(&mut *rng);
+    let cipher = encrypt(message, shared_secret, &nonce.into())
+        .expect("encryption should pass");
+
+    let mut circuit = VerifyCipherCircuit::<L> {
+        cipher,
+        shared_secret,
+        nonce,
+    };
+    let (proof, public_inputs) = prover.prove(rng, &circuit)?;
+    verifier.verify(&proof, &public_inputs)?;
+
+    // a tampered cipher fails
+    circuit.cipher[0] += BlsScalar::one();
+    assert!(prover.prove(rng, &circuit).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn verify_cipher_single_rate() -> Result<(), PlonkError> {
+    verify_cipher::<2>(&mut StdRng::seed_from_u64(0x42424242))
+}
+
+#[test]
+fn verify_cipher_multiple_rates() -> Result<(), PlonkError> {
+    verify_cipher::<9>(&mut StdRng::seed_from_u64(0x42424242))
+}
+
+#[test]
+fn verify_cipher_constraints() {
+    let mut composer = Composer::initialized();
+    let cipher = [composer.append_witness(BlsScalar::one()); 10];
+    let shared_secret = composer.append_point(JubJubAffine::identity());
+    let nonce = composer.append_witness(BlsScalar::one());
+
+    let initial = composer.constraints();
+    decrypt_gadget(&mut composer, cipher, &shared_secret, &nonce)
+        .expect("the cipher length is valid");
+    let decrypt = composer.constraints() - initial;
+
+    // verifying the cipher saves one constraint per message element
+    let initial = composer.constraints();
+    verify_cipher_gadget(&mut composer, cipher, &shared_secret, &nonce)
+        .expect("the cipher length is valid");
+    assert_eq!(composer.constraints() - initial, decrypt - 9);
+
+    // no cipher-text too short for a message can be verified
+    assert!(verify_cipher_gadget(
+        &mut composer,
+        &cipher[..1],
+        &shared_secret,
+        &nonce
+    )
+    .is_err());
+}
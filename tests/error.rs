@@ -0,0 +1,63 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_poseidon::Error;
+
+const ERRORS: [Error; 6] = [
+    Error::IOPatternViolation,
+    Error::InvalidIOPattern,
+    Error::TooFewInputElements,
+    Error::EncryptionFailed,
+    Error::DecryptionFailed,
+    Error::InvalidPoint,
+];
+
+#[test]
+fn codes() {
+    // the codes are stable
+    assert_eq!(Error::InvalidPoint.code(), 4);
+    assert_eq!(Error::DecryptionFailed.code(), 5);
+    assert_eq!(Error::EncryptionFailed.code(), 6);
+    assert_eq!(Error::IOPatternViolation.code(), 7);
+    assert_eq!(Error::InvalidIOPattern.code(), 8);
+    assert_eq!(Error::TooFewInputElements.code(), 9);
+
+    // and unique
+    for (i, a) in ERRORS.iter().enumerate() {
+        for b in ERRORS.iter().skip(i + 1) {
+            assert_ne!(a.code(), b.code());
+        }
+    }
+}
+
+#[test]
+fn display() {
+    assert_eq!(
+        format!("{}", Error::DecryptionFailed),
+        "the decryption failed (code 5)"
+    );
+}
+
+#[cfg(feature = "hostfn")]
+#[test]
+fn hostfn_codes() {
+    use dusk_poseidon::hostfn;
+
+    assert_eq!(hostfn::INVALID_POINT, Error::InvalidPoint.code());
+    assert_eq!(hostfn::DECRYPTION_FAILED, Error::DecryptionFailed.code());
+
+    // no error shares its code with another status of the host functions
+    for code in [
+        hostfn::SUCCESS,
+        hostfn::INVALID_INPUT_LENGTH,
+        hostfn::INVALID_OUTPUT_LENGTH,
+        hostfn::INVALID_SCALAR,
+        hostfn::INVALID_OPENING,
+        hostfn::INVALID_POSITION,
+    ] {
+        assert!(ERRORS.iter().all(|e| e.code() != code));
+    }
+}
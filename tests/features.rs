@@ -0,0 +1,19 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_poseidon::features::*;
+
+#[test]
+fn features() {
+    assert_eq!(has_zk(), cfg!(feature = "zk"));
+    assert_eq!(has_encryption(), cfg!(feature = "encryption"));
+    assert_eq!(has_hostfn(), cfg!(feature = "hostfn"));
+    assert_eq!(has_hybrid(), cfg!(feature = "hybrid"));
+    assert_eq!(has_metrics(), cfg!(feature = "metrics"));
+    assert_eq!(has_std_hash(), cfg!(feature = "std-hash"));
+    assert_eq!(has_std(), cfg!(feature = "std"));
+    assert_eq!(has_debug_gadgets(), cfg!(feature = "debug-gadgets"));
+}
@@ -0,0 +1,81 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#![cfg(feature = "zk")]
+
+use once_cell::sync::Lazy;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use dusk_plonk::prelude::Error as PlonkError;
+use dusk_plonk::prelude::*;
+use dusk_poseidon::gadgets::decompose;
+
+static PUB_PARAMS: Lazy<PublicParameters> = Lazy::new(|| {
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+
+    const CAPACITY: usize = 9;
+    PublicParameters::setup(1 << CAPACITY, &mut rng)
+        .expect("Setup of public params should pass")
+});
+
+#[derive(Debug, Default)]
+struct DecomposeCircuit<const BITS: usize> {
+    value: BlsScalar,
+}
+
+impl<const BITS: usize> Circuit for DecomposeCircuit<BITS> {
+    fn circuit(&self, composer: &mut Composer) -> Result<(), PlonkError> {
+        let value = composer.append_witness(self.value);
+        let bits = decompose(composer, value, BITS);
+        assert_eq!(bits.len(), BITS);
+
+        // the bits are little-endian
+        let bytes = self.value.to_bytes();
+        bits.iter().enumerate().for_each(|(i, bit)| {
+            let expected = (bytes[i / 8] >> (i % 8)) & 1;
+            assert_eq!(composer[*bit], BlsScalar::from(expected as u64));
+        });
+
+        Ok(())
+    }
+}
+
+fn prove_and_verify<const BITS: usize>(
+    value: BlsScalar,
+) -> Result<(), PlonkError> {
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+
+    let label = b"decompose-tester";
+    let (prover, verifier) =
+        Compiler::compile::<DecomposeCircuit<BITS>>(&PUB_PARAMS, label)?;
+
+    let circuit = DecomposeCircuit::<BITS> { value };
+    let (proof, public_inputs) = prover.prove(&mut rng, &circuit)?;
+
+    verifier.verify(&proof, &public_inputs)
+}
+
+#[test]
+fn decompose_value() -> Result<(), PlonkError> {
+    prove_and_verify::<64>(BlsScalar::from(0xdead_beef_u64))?;
+    prove_and_verify::<64>(BlsScalar::from(u64::MAX))?;
+    prove_and_verify::<33>(BlsScalar::from(1u64 << 32))
+}
+
+#[test]
+fn decompose_out_of_bounds() {
+    let value = BlsScalar::from(1u64 << 32);
+    assert!(prove_and_verify::<32>(value).is_err());
+}
+
+#[test]
+#[should_panic]
+fn decompose_too_many_bits() {
+    let mut composer = Composer::initialized();
+    let value = composer.append_witness(BlsScalar::one());
+    decompose(&mut composer, value, 255);
+}
@@ -12,13 +12,18 @@ use rand::SeedableRng;
 
 use dusk_plonk::prelude::Error as PlonkError;
 use dusk_plonk::prelude::*;
-use dusk_poseidon::{Domain, Hash, HashGadget};
+use dusk_poseidon::{
+    assert_digest_eq, compress, compress_gadget, embed_jubjub,
+    embed_jubjub_gadget, hash_rows, level_hash, level_hash_gadget,
+    ConstantCache, Domain, DomainKind, Hash, HashGadget,
+};
+use dusk_poseidon::{is_reserved_encoding, RESERVED_ENCODINGS};
 use ff::Field;
 
 static PUB_PARAMS: Lazy<PublicParameters> = Lazy::new(|| {
     let mut rng = StdRng::seed_from_u64(0xbeef);
 
-    const CAPACITY: usize = 12;
+    const CAPACITY: usize = 13;
     PublicParameters::setup(1 << CAPACITY, &mut rng)
         .expect("Setup of public params should pass")
 });
@@ -26,7 +31,7 @@ static PUB_PARAMS: Lazy<PublicParameters> = Lazy::new(|| {
 fn compile_and_verify<C>(
     rng: &mut StdRng,
     circuit: &C,
-    pi: &Vec<BlsScalar>,
+    pi: &[BlsScalar],
 ) -> Result<(), PlonkError>
 where
     C: Circuit,
@@ -292,3 +297,676 @@ fn test_multiple_output() -> Result<(), Error> {
     let circuit = MultipleOutputCircuit::<4, 7>::random(&mut rng);
     compile_and_verify(&mut rng, &circuit, &circuit.public_inputs())
 }
+
+// ---------------------
+// Test digest assertion
+// ---------------------
+
+#[derive(Debug, Default)]
+struct AssertDigestCircuit<const L: usize> {
+    circuit: TestCircuit<L>,
+}
+
+impl<const L: usize> Circuit for AssertDigestCircuit<L> {
+    fn circuit(&self, composer: &mut Composer) -> Result<(), PlonkError> {
+        // append input to the circuit
+        let mut input_witnesses = [Composer::ZERO; L];
+        self.circuit
+            .input
+            .iter()
+            .zip(input_witnesses.iter_mut())
+            .for_each(|(i, w)| {
+                *w = composer.append_witness(*i);
+            });
+
+        // check that the digest of the input is as expected
+        assert_digest_eq(
+            composer,
+            Domain::Other,
+            &input_witnesses,
+            self.circuit.output,
+        );
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_assert_digest_eq() -> Result<(), Error> {
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+
+    let circuit = AssertDigestCircuit {
+        circuit: TestCircuit::<5>::random(&mut rng),
+    };
+    compile_and_verify(&mut rng, &circuit, &circuit.circuit.public_inputs())?;
+
+    // verification fails with a different digest
+    let wrong_pi = [BlsScalar::random(&mut rng)];
+    assert!(compile_and_verify(&mut rng, &circuit, &wrong_pi).is_err());
+
+    Ok(())
+}
+
+static PREIMAGE_DIGEST: Lazy<BlsScalar> =
+    Lazy::new(|| Hash::digest(Domain::Other, &[BlsScalar::from(42)])[0]);
+
+#[derive(Debug, Default)]
+struct PreimageCircuit {
+    preimage: BlsScalar,
+}
+
+impl Circuit for PreimageCircuit {
+    fn circuit(&self, composer: &mut Composer) -> Result<(), PlonkError> {
+        let preimage = [composer.append_witness(self.preimage)];
+
+        HashGadget::new(Domain::Other)
+            .update(&preimage)
+            .finalize_expect(composer, *PREIMAGE_DIGEST);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_finalize_expect() -> Result<(), Error> {
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+
+    let circuit = PreimageCircuit {
+        preimage: BlsScalar::from(42),
+    };
+    compile_and_verify(&mut rng, &circuit, &[])?;
+
+    // proving fails with a different preimage
+    let circuit = PreimageCircuit {
+        preimage: BlsScalar::from(43),
+    };
+    assert!(compile_and_verify(&mut rng, &circuit, &[]).is_err());
+
+    Ok(())
+}
+
+// -------------------
+// Test versioned hash
+// -------------------
+
+#[derive(Debug)]
+struct VersionedCircuit<const L: usize> {
+    input: [BlsScalar; L],
+    output: BlsScalar,
+}
+
+impl<const L: usize> Default for VersionedCircuit<L> {
+    fn default() -> Self {
+        Self {
+            input: [BlsScalar::zero(); L],
+            output: BlsScalar::zero(),
+        }
+    }
+}
+
+impl<const L: usize> VersionedCircuit<L> {
+    pub fn random(rng: &mut StdRng) -> Self {
+        let input = core::array::from_fn(|_| BlsScalar::random(&mut *rng));
+
+        let mut hash = Hash::versioned(Domain::Other);
+        hash.update(&input);
+        let output = hash.finalize()[0];
+
+        Self { input, output }
+    }
+}
+
+impl<const L: usize> Circuit for VersionedCircuit<L> {
+    fn circuit(&self, composer: &mut Composer) -> Result<(), PlonkError> {
+        let input_witnesses: [Witness; L] =
+            core::array::from_fn(|i| composer.append_witness(self.input[i]));
+
+        let mut hash = HashGadget::versioned(Domain::Other);
+        hash.update(&input_witnesses);
+        let gadget_output = hash.finalize(composer);
+        composer.assert_equal_constant(gadget_output[0], 0, Some(self.output));
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_versioned_gadget() -> Result<(), Error> {
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+
+    let circuit = VersionedCircuit::<5>::random(&mut rng);
+    compile_and_verify(&mut rng, &circuit, &[circuit.output])?;
+
+    // the versioned digest differs from the plain digest
+    let plain = Hash::digest(Domain::Other, &circuit.input)[0];
+    assert!(compile_and_verify(&mut rng, &circuit, &[plain]).is_err());
+
+    Ok(())
+}
+
+// ---------------
+// Test level hash
+// ---------------
+
+#[derive(Debug)]
+struct LevelCircuit<const A: usize> {
+    level: [BlsScalar; A],
+    output: BlsScalar,
+}
+
+impl<const A: usize> Default for LevelCircuit<A> {
+    fn default() -> Self {
+        Self {
+            level: [BlsScalar::zero(); A],
+            output: BlsScalar::zero(),
+        }
+    }
+}
+
+impl<const A: usize> LevelCircuit<A> {
+    pub fn random(rng: &mut StdRng) -> Self {
+        let level = core::array::from_fn(|_| BlsScalar::random(&mut *rng));
+        let output = level_hash(&level);
+
+        Self { level, output }
+    }
+}
+
+impl<const A: usize> Circuit for LevelCircuit<A> {
+    fn circuit(&self, composer: &mut Composer) -> Result<(), PlonkError> {
+        let level: [Witness; A] =
+            core::array::from_fn(|i| composer.append_witness(self.level[i]));
+
+        let output = level_hash_gadget(composer, &level);
+        composer.assert_equal_constant(output, 0, Some(self.output));
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_level_hash() -> Result<(), Error> {
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+
+    let circuit = LevelCircuit::<2>::random(&mut rng);
+    assert_eq!(
+        circuit.output,
+        Hash::digest(Domain::Merkle2, &circuit.level)[0]
+    );
+    compile_and_verify(&mut rng, &circuit, &[circuit.output])?;
+
+    let circuit = LevelCircuit::<4>::random(&mut rng);
+    assert_eq!(
+        circuit.output,
+        Hash::digest(Domain::Merkle4, &circuit.level)[0]
+    );
+    compile_and_verify(&mut rng, &circuit, &[circuit.output])
+}
+
+#[test]
+#[should_panic]
+fn test_level_hash_arity() {
+    level_hash(&[BlsScalar::one(); 3]);
+}
+
+// --------------
+// Test hash rows
+// --------------
+
+#[test]
+fn test_hash_rows() {
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+    let rows: Vec<[BlsScalar; 4]> = (0..5)
+        .map(|_| core::array::from_fn(|_| BlsScalar::random(&mut rng)))
+        .collect();
+
+    let expected: Vec<BlsScalar> = rows
+        .iter()
+        .map(|row| Hash::digest(Domain::Merkle4, row)[0])
+        .collect();
+    assert_eq!(hash_rows(Domain::Merkle4, &rows), expected);
+
+    let expected: Vec<BlsScalar> = rows
+        .iter()
+        .map(|row| Hash::digest(Domain::Other, row)[0])
+        .collect();
+    assert_eq!(hash_rows(Domain::Other, &rows), expected);
+
+    assert!(hash_rows(Domain::Other, &[] as &[[BlsScalar; 4]]).is_empty());
+}
+
+// ------------------
+// Test custom domain
+// ------------------
+
+// A domain for pairs of scalars, hashed into one scalar.
+struct PairDomain;
+
+impl PairDomain {
+    const ENCODING: u64 = 0x7061_6972;
+}
+
+const _: () = assert!(!is_reserved_encoding(PairDomain::ENCODING));
+
+impl DomainKind for PairDomain {
+    fn validate(
+        &self,
+        input_len: usize,
+        output_len: usize,
+    ) -> Result<(), dusk_poseidon::Error> {
+        match input_len == 2 && output_len == 1 {
+            true => Ok(()),
+            false => Err(dusk_poseidon::Error::IOPatternViolation),
+        }
+    }
+
+    fn tag_input(&self) -> u64 {
+        Self::ENCODING
+    }
+}
+
+#[test]
+fn test_custom_domain() {
+    let input = [BlsScalar::one(), BlsScalar::from(2)];
+
+    let digest = Hash::digest(PairDomain, &input);
+    assert_eq!(digest.len(), 1);
+    assert_ne!(digest, Hash::digest(Domain::Other, &input));
+    assert_ne!(digest, Hash::digest(Domain::Merkle2, &input));
+}
+
+#[test]
+fn test_domain_encodings() {
+    assert_eq!(Domain::Merkle4.encoding(), 0xf);
+    assert_eq!(Domain::Merkle2.encoding(), 0x3);
+    assert_eq!(Domain::Encryption.encoding(), 1 << 32);
+    assert_eq!(Domain::Other.encoding(), 0);
+
+    for domain in [
+        Domain::Merkle4,
+        Domain::Merkle2,
+        Domain::Encryption,
+        Domain::Other,
+    ] {
+        assert_eq!(domain.encoding(), u64::from(domain));
+        assert_eq!(domain.encoding(), domain.tag_input());
+        assert!(is_reserved_encoding(domain.encoding()));
+    }
+
+    // the reserved encodings don't overlap
+    for (i, (_, a)) in RESERVED_ENCODINGS.iter().enumerate() {
+        for (_, b) in RESERVED_ENCODINGS.iter().skip(i + 1) {
+            assert!(a.end <= b.start || b.end <= a.start);
+        }
+    }
+
+    assert!(is_reserved_encoding((1 << 33) + 2));
+    assert!(is_reserved_encoding(1 << 34));
+    assert!(!is_reserved_encoding(1));
+    assert!(!is_reserved_encoding((1 << 34) + 1));
+}
+
+#[test]
+#[should_panic]
+fn test_custom_domain_validation() {
+    Hash::digest(PairDomain, &[BlsScalar::one()]);
+}
+
+#[derive(Debug, Default)]
+struct PairCircuit {
+    input: [BlsScalar; 2],
+}
+
+impl Circuit for PairCircuit {
+    fn circuit(&self, composer: &mut Composer) -> Result<(), PlonkError> {
+        let input = self.input.map(|i| composer.append_witness(i));
+        let expected = Hash::digest(PairDomain, &self.input)[0];
+
+        assert_digest_eq(composer, PairDomain, &input, expected);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_custom_domain_gadget() -> Result<(), Error> {
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+
+    let input = [BlsScalar::one(), BlsScalar::from(2)];
+    let circuit = PairCircuit { input };
+    let expected = Hash::digest(PairDomain, &input);
+    compile_and_verify(&mut rng, &circuit, &expected)
+}
+
+// A domain squeezing as many scalars as it absorbs.
+struct EchoDomain;
+
+impl EchoDomain {
+    const ENCODING: u64 = 0x6563_686f;
+}
+
+const _: () = assert!(!is_reserved_encoding(EchoDomain::ENCODING));
+
+impl DomainKind for EchoDomain {
+    fn validate(
+        &self,
+        input_len: usize,
+        output_len: usize,
+    ) -> Result<(), dusk_poseidon::Error> {
+        match input_len == output_len {
+            true => Ok(()),
+            false => Err(dusk_poseidon::Error::IOPatternViolation),
+        }
+    }
+
+    fn tag_input(&self) -> u64 {
+        Self::ENCODING
+    }
+}
+
+#[test]
+fn test_custom_domain_output_len_before_update() {
+    let input = [BlsScalar::one(), BlsScalar::from(2), BlsScalar::from(3)];
+
+    let mut hash = Hash::new(EchoDomain);
+    hash.output_len(3).update(&input);
+    let output = hash.finalize();
+    assert_eq!(output.len(), 3);
+
+    let mut hash = Hash::new(EchoDomain);
+    hash.update(&input).output_len(3);
+    assert_eq!(hash.finalize(), output);
+}
+
+#[test]
+#[should_panic]
+fn test_custom_domain_output_len_validation() {
+    let input = [BlsScalar::one(), BlsScalar::from(2)];
+
+    let mut hash = Hash::new(EchoDomain);
+    hash.output_len(3).update(&input);
+    hash.finalize();
+}
+
+// --------------------------
+// Test cached hash constants
+// --------------------------
+
+#[derive(Debug)]
+struct CachedCircuit<const L: usize> {
+    input: [BlsScalar; L],
+    output: BlsScalar,
+}
+
+impl<const L: usize> Default for CachedCircuit<L> {
+    fn default() -> Self {
+        Self {
+            input: [BlsScalar::zero(); L],
+            output: BlsScalar::zero(),
+        }
+    }
+}
+
+impl<const L: usize> Circuit for CachedCircuit<L> {
+    fn circuit(&self, composer: &mut Composer) -> Result<(), PlonkError> {
+        let input: [Witness; L] =
+            core::array::from_fn(|i| composer.append_witness(self.input[i]));
+
+        let mut cache = ConstantCache::new();
+
+        // hash the input twice, reusing the cached domain tag
+        let mut hash = HashGadget::new(Domain::Other);
+        hash.update(&input);
+        let first = hash.finalize_cached(composer, &mut cache);
+        let second = hash.finalize_cached(composer, &mut cache);
+        assert_eq!(cache.len(), 1);
+
+        composer.assert_equal(first[0], second[0]);
+        composer.assert_equal_constant(second[0], 0, Some(self.output));
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_cached_gadget() -> Result<(), Error> {
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+
+    let input = core::array::from_fn(|_| BlsScalar::random(&mut rng));
+    let output = Hash::digest(Domain::Other, &input)[0];
+    let circuit = CachedCircuit::<3> { input, output };
+
+    compile_and_verify(&mut rng, &circuit, &[output])
+}
+
+#[test]
+fn test_cached_constraints() {
+    let input = [BlsScalar::one(); 2];
+
+    let mut composer = Composer::initialized();
+    let input: [Witness; 2] =
+        core::array::from_fn(|i| composer.append_witness(input[i]));
+
+    let mut hash = HashGadget::new(Domain::Other);
+    hash.update(&input);
+
+    let initial = composer.constraints();
+    hash.finalize(&mut composer);
+    let uncached = composer.constraints() - initial;
+
+    let mut cache = ConstantCache::new();
+    hash.finalize_cached(&mut composer, &mut cache);
+
+    // the second cached hash doesn't append the domain tag again
+    let initial = composer.constraints();
+    hash.finalize_cached(&mut composer, &mut cache);
+    assert_eq!(composer.constraints() - initial, uncached - 1);
+}
+
+// -------------------
+// Test column digests
+// -------------------
+
+#[derive(Debug)]
+struct ColumnsCircuit<const K: usize> {
+    columns: [[BlsScalar; K]; 3],
+    outputs: [BlsScalar; K],
+}
+
+impl<const K: usize> Default for ColumnsCircuit<K> {
+    fn default() -> Self {
+        Self {
+            columns: [[BlsScalar::zero(); K]; 3],
+            outputs: [BlsScalar::zero(); K],
+        }
+    }
+}
+
+impl<const K: usize> Circuit for ColumnsCircuit<K> {
+    fn circuit(&self, composer: &mut Composer) -> Result<(), PlonkError> {
+        let columns: [[Witness; K]; 3] = core::array::from_fn(|j| {
+            core::array::from_fn(|k| {
+                composer.append_witness(self.columns[j][k])
+            })
+        });
+        let columns: Vec<&[Witness]> =
+            columns.iter().map(|column| &column[..]).collect();
+
+        let outputs =
+            HashGadget::digest_columns(composer, Domain::Other, &columns);
+        assert_eq!(outputs.len(), K);
+        outputs.iter().zip(self.outputs.iter()).for_each(|(w, o)| {
+            composer.assert_equal_constant(*w, 0, Some(*o));
+        });
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_digest_columns() -> Result<(), Error> {
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+
+    let columns: [[BlsScalar; 2]; 3] = core::array::from_fn(|_| {
+        core::array::from_fn(|_| BlsScalar::random(&mut rng))
+    });
+    let rows: Vec<[BlsScalar; 3]> = (0..2)
+        .map(|k| core::array::from_fn(|j| columns[j][k]))
+        .collect();
+    let outputs = hash_rows(Domain::Other, &rows);
+    let circuit = ColumnsCircuit::<2> {
+        columns,
+        outputs: [outputs[0], outputs[1]],
+    };
+
+    compile_and_verify(&mut rng, &circuit, &outputs)
+}
+
+#[test]
+#[should_panic]
+fn test_digest_columns_unequal() {
+    let mut composer = Composer::initialized();
+    let w = composer.append_witness(BlsScalar::one());
+
+    HashGadget::digest_columns(&mut composer, Domain::Other, &[&[w, w], &[w]]);
+}
+
+// ----------------------
+// Test jubjub embeddings
+// ----------------------
+
+#[derive(Debug, Default)]
+struct EmbedCircuit {
+    value: BlsScalar,
+}
+
+impl Circuit for EmbedCircuit {
+    fn circuit(&self, composer: &mut Composer) -> Result<(), PlonkError> {
+        let value = composer.append_witness(self.value);
+        let embedded = embed_jubjub_gadget(composer, value);
+        composer.assert_equal_constant(embedded, 0, Some(self.value));
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_embed_jubjub() -> Result<(), Error> {
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+
+    let scalar = JubJubScalar::random(&mut rng);
+    let value = embed_jubjub(&scalar);
+    assert_eq!(value.to_bytes(), scalar.to_bytes());
+    compile_and_verify(&mut rng, &EmbedCircuit { value }, &[value])?;
+
+    // the largest jubjub-scalar is canonical
+    let value = embed_jubjub(&-JubJubScalar::one());
+    compile_and_verify(&mut rng, &EmbedCircuit { value }, &[value])?;
+
+    // the order of the jubjub scalar field isn't
+    let value = value + BlsScalar::one();
+    assert!(
+        compile_and_verify(&mut rng, &EmbedCircuit { value }, &[value])
+            .is_err()
+    );
+
+    Ok(())
+}
+
+// -------------------------
+// Test typed merkle digests
+// -------------------------
+
+#[derive(Debug, Default)]
+struct MerkleCircuit {
+    level2: [BlsScalar; 2],
+    level4: [BlsScalar; 4],
+    output2: BlsScalar,
+    output4: BlsScalar,
+}
+
+impl Circuit for MerkleCircuit {
+    fn circuit(&self, composer: &mut Composer) -> Result<(), PlonkError> {
+        let level2 = self.level2.map(|s| composer.append_witness(s));
+        let level4 = self.level4.map(|s| composer.append_witness(s));
+
+        let output2 = HashGadget::digest_merkle2(composer, &level2);
+        let output4 = HashGadget::digest_merkle4(composer, &level4);
+        composer.assert_equal_constant(output2, 0, Some(self.output2));
+        composer.assert_equal_constant(output4, 0, Some(self.output4));
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_digest_merkle() -> Result<(), Error> {
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+
+    let level2 = core::array::from_fn(|_| BlsScalar::random(&mut rng));
+    let level4 = core::array::from_fn(|_| BlsScalar::random(&mut rng));
+    let output2 = Hash::digest_merkle2(&level2);
+    let output4 = Hash::digest_merkle4(&level4);
+    assert_eq!(output2, Hash::digest(Domain::Merkle2, &level2)[0]);
+    assert_eq!(output4, Hash::digest(Domain::Merkle4, &level4)[0]);
+
+    let circuit = MerkleCircuit {
+        level2,
+        level4,
+        output2,
+        output4,
+    };
+    compile_and_verify(&mut rng, &circuit, &[output2, output4])
+}
+
+// --------------------
+// Test two-to-one hash
+// --------------------
+
+#[derive(Debug, Default)]
+struct CompressCircuit {
+    a: BlsScalar,
+    b: BlsScalar,
+    output: BlsScalar,
+}
+
+impl Circuit for CompressCircuit {
+    fn circuit(&self, composer: &mut Composer) -> Result<(), PlonkError> {
+        let a = composer.append_witness(self.a);
+        let b = composer.append_witness(self.b);
+
+        let output = compress_gadget(composer, a, b);
+        composer.assert_equal_constant(output, 0, Some(self.output));
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_compress() -> Result<(), Error> {
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+
+    let a = BlsScalar::random(&mut rng);
+    let b = BlsScalar::random(&mut rng);
+    let output = compress(&a, &b);
+    assert_eq!(output, Hash::digest(Domain::Merkle2, &[a, b])[0]);
+
+    let circuit = CompressCircuit { a, b, output };
+    compile_and_verify(&mut rng, &circuit, &[output])
+}
+
+#[test]
+fn test_compress_constraints() {
+    let mut composer = Composer::initialized();
+    let a = composer.append_witness(BlsScalar::one());
+    let b = composer.append_witness(BlsScalar::one());
+
+    let initial = composer.constraints();
+    HashGadget::digest(&mut composer, Domain::Merkle2, &[a, b]);
+    let digest = composer.constraints() - initial;
+
+    // compressing doesn't cost more than the sponge
+    let initial = composer.constraints();
+    compress_gadget(&mut composer, a, b);
+    assert!(composer.constraints() - initial <= digest);
+}
@@ -0,0 +1,47 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#![cfg(feature = "std-hash")]
+
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+
+use dusk_poseidon::{PoseidonBuildHasher, PoseidonHasher};
+
+fn hash_bytes(bytes: &[u8]) -> u64 {
+    let mut hasher = PoseidonHasher::new();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+#[test]
+fn hasher() {
+    assert_eq!(hash_bytes(b"poseidon"), hash_bytes(b"poseidon"));
+    assert_ne!(hash_bytes(b"poseidon"), hash_bytes(b"poseidoN"));
+
+    // the length of the input is encoded
+    assert_ne!(hash_bytes(&[]), hash_bytes(&[0]));
+    assert_ne!(hash_bytes(&[0; 31]), hash_bytes(&[0; 32]));
+
+    // writing in several calls is the same as writing at once
+    let mut hasher = PoseidonBuildHasher.build_hasher();
+    hasher.write(&[1; 20]);
+    hasher.write(&[2; 20]);
+    let mut bytes = vec![1; 20];
+    bytes.extend_from_slice(&[2; 20]);
+    assert_eq!(hasher.finish(), hash_bytes(&bytes));
+}
+
+#[test]
+fn hash_map() {
+    let mut map = HashMap::with_hasher(PoseidonBuildHasher);
+    map.insert("one", 1);
+    map.insert("two", 2);
+
+    assert_eq!(map.get("one"), Some(&1));
+    assert_eq!(map.get("two"), Some(&2));
+    assert_eq!(map.get("three"), None);
+}
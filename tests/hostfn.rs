@@ -0,0 +1,476 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#![cfg(feature = "hostfn")]
+
+use dusk_bls12_381::BlsScalar;
+use dusk_poseidon::hostfn::*;
+use dusk_poseidon::merkle::{opening, root_from_leaves};
+use dusk_poseidon::{Domain, Hash};
+use ff::Field;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn scalars_to_bytes(scalars: &[BlsScalar]) -> Vec<u8> {
+    scalars.iter().flat_map(|s| s.to_bytes()).collect()
+}
+
+#[test]
+fn c_abi() {
+    let input = scalars_to_bytes(&[BlsScalar::one(), BlsScalar::from(2)]);
+
+    let mut output = [0u8; 32];
+    let mut expected = [0u8; 32];
+    let code = unsafe {
+        dusk_poseidon_hash(input.as_ptr(), input.len(), output.as_mut_ptr())
+    };
+    assert_eq!(code, SUCCESS);
+    assert_eq!(poseidon_hash(&input, &mut expected), SUCCESS);
+    assert_eq!(output, expected);
+
+    let code = unsafe {
+        dusk_poseidon_hash_truncated(
+            input.as_ptr(),
+            input.len(),
+            output.as_mut_ptr(),
+        )
+    };
+    assert_eq!(code, SUCCESS);
+    assert_eq!(poseidon_hash_truncated(&input, &mut expected), SUCCESS);
+    assert_eq!(output, expected);
+
+    let code = unsafe {
+        dusk_poseidon_merkle_root(
+            input.as_ptr(),
+            input.len(),
+            1,
+            output.as_mut_ptr(),
+        )
+    };
+    assert_eq!(code, SUCCESS);
+    assert_eq!(poseidon_merkle_root(&input, 1, &mut expected), SUCCESS);
+    assert_eq!(output, expected);
+
+    // the status codes are the same
+    let code =
+        unsafe { dusk_poseidon_hash(input.as_ptr(), 31, output.as_mut_ptr()) };
+    assert_eq!(code, INVALID_INPUT_LENGTH);
+}
+
+#[test]
+fn hash() {
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+    let input: Vec<BlsScalar> =
+        (0..7).map(|_| BlsScalar::random(&mut rng)).collect();
+
+    let mut output = [0u8; 32];
+    assert_eq!(
+        poseidon_hash(&scalars_to_bytes(&input), &mut output),
+        SUCCESS
+    );
+
+    let expected = Hash::digest(Domain::Other, &input)[0];
+    assert_eq!(output, expected.to_bytes());
+}
+
+#[test]
+fn hash_invalid_input() {
+    let mut output = [0u8; 32];
+
+    assert_eq!(poseidon_hash(&[], &mut output), INVALID_INPUT_LENGTH);
+    assert_eq!(poseidon_hash(&[0u8; 33], &mut output), INVALID_INPUT_LENGTH);
+    assert_eq!(poseidon_hash(&[0xff; 32], &mut output), INVALID_SCALAR);
+    assert_eq!(output, [0u8; 32]);
+}
+
+#[test]
+fn hash_max_input() {
+    let input = vec![BlsScalar::one(); MAX_INPUT_SCALARS];
+
+    let mut output = [0u8; 32];
+    assert_eq!(
+        poseidon_hash(&scalars_to_bytes(&input), &mut output),
+        SUCCESS
+    );
+    assert_eq!(output, Hash::digest(Domain::Other, &input)[0].to_bytes());
+
+    let input = vec![BlsScalar::one(); MAX_INPUT_SCALARS + 1];
+    assert_eq!(
+        poseidon_hash(&scalars_to_bytes(&input), &mut output),
+        INVALID_INPUT_LENGTH
+    );
+}
+
+#[test]
+fn hash_truncated() {
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+    let input: Vec<BlsScalar> =
+        (0..3).map(|_| BlsScalar::random(&mut rng)).collect();
+
+    let mut output = [0u8; 32];
+    assert_eq!(
+        poseidon_hash_truncated(&scalars_to_bytes(&input), &mut output),
+        SUCCESS
+    );
+
+    let expected = Hash::digest_truncated(Domain::Other, &input)[0];
+    assert_eq!(output, expected.to_bytes());
+
+    let mut output = [0u8; 32];
+    assert_eq!(
+        poseidon_hash_truncated(&[0u8; 31], &mut output),
+        INVALID_INPUT_LENGTH
+    );
+    assert_eq!(output, [0u8; 32]);
+}
+
+#[test]
+fn merkle_root() {
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+    let leaves: Vec<BlsScalar> =
+        (0..300).map(|_| BlsScalar::random(&mut rng)).collect();
+
+    for (len, height) in [(1, 0), (5, 3), (16, 2), (17, 5), (300, 5)] {
+        let leaves = &leaves[..len];
+        let mut output = [0u8; 32];
+        assert_eq!(
+            poseidon_merkle_root(
+                &scalars_to_bytes(leaves),
+                height,
+                &mut output
+            ),
+            SUCCESS
+        );
+
+        let expected =
+            root_from_leaves::<4>(height as usize, leaves.iter().copied());
+        assert_eq!(output, expected.to_bytes());
+    }
+}
+
+#[test]
+fn merkle_root_invalid_input() {
+    let leaves = scalars_to_bytes(&[BlsScalar::one(); 5]);
+    let mut output = [0u8; 32];
+
+    // a tree of height 1 has only 4 positions
+    assert_eq!(
+        poseidon_merkle_root(&leaves, 1, &mut output),
+        INVALID_INPUT_LENGTH
+    );
+    assert_eq!(
+        poseidon_merkle_root(&leaves, MAX_MERKLE_HEIGHT + 1, &mut output),
+        INVALID_INPUT_LENGTH
+    );
+    assert_eq!(
+        poseidon_merkle_root(&[], 2, &mut output),
+        INVALID_INPUT_LENGTH
+    );
+    assert_eq!(output, [0u8; 32]);
+
+    // the leaves are decoded as they are hashed
+    let mut leaves = leaves;
+    leaves[64..96].copy_from_slice(&[0xff; 32]);
+    assert_eq!(
+        poseidon_merkle_root(&leaves, 2, &mut output),
+        INVALID_SCALAR
+    );
+    assert_eq!(output, [0u8; 32]);
+}
+
+#[test]
+fn merkle_opening_verify() {
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+    let leaves: Vec<BlsScalar> =
+        (0..17).map(|_| BlsScalar::random(&mut rng)).collect();
+    let bytes = scalars_to_bytes(&leaves);
+    let height = 3;
+
+    let mut root = [0u8; 32];
+    assert_eq!(poseidon_merkle_root(&bytes, height, &mut root), SUCCESS);
+
+    for position in [0, 5, 16] {
+        let mut output = vec![0u8; height as usize * 4 * 32];
+        assert_eq!(
+            poseidon_merkle_opening(&bytes, height, position, &mut output),
+            SUCCESS
+        );
+
+        let expected =
+            opening::<4>(height as usize, leaves.iter().copied(), position);
+        let expected: Vec<BlsScalar> = expected.concat();
+        assert_eq!(output, scalars_to_bytes(&expected));
+
+        let leaf = leaves[position as usize].to_bytes();
+        assert_eq!(
+            poseidon_merkle_verify(&root, &leaf, position, &output),
+            SUCCESS
+        );
+        assert_eq!(
+            poseidon_merkle_verify(&root, &leaf, position + 1, &output),
+            INVALID_OPENING
+        );
+        let wrong_leaf = leaves[position as usize] + BlsScalar::one();
+        assert_eq!(
+            poseidon_merkle_verify(
+                &root,
+                &wrong_leaf.to_bytes(),
+                position,
+                &output
+            ),
+            INVALID_OPENING
+        );
+    }
+}
+
+#[test]
+fn merkle_opening_verify_invalid_input() {
+    let bytes = scalars_to_bytes(&[BlsScalar::one(); 5]);
+    let leaf = BlsScalar::one().to_bytes();
+    let mut output = vec![0u8; 2 * 4 * 32];
+
+    assert_eq!(
+        poseidon_merkle_opening(&bytes, 2, 5, &mut output),
+        INVALID_POSITION
+    );
+    assert_eq!(
+        poseidon_merkle_opening(&bytes, 1, 0, &mut output),
+        INVALID_INPUT_LENGTH
+    );
+    assert_eq!(
+        poseidon_merkle_opening(&bytes, 2, 0, &mut output[32..]),
+        INVALID_OUTPUT_LENGTH
+    );
+    assert_eq!(output, vec![0u8; 2 * 4 * 32]);
+
+    let mut root = [0u8; 32];
+    assert_eq!(poseidon_merkle_root(&bytes, 2, &mut root), SUCCESS);
+    assert_eq!(poseidon_merkle_opening(&bytes, 2, 4, &mut output), SUCCESS);
+    assert_eq!(
+        poseidon_merkle_verify(&root, &leaf, 4, &output[32..]),
+        INVALID_INPUT_LENGTH
+    );
+    assert_eq!(
+        poseidon_merkle_verify(&[0xff; 32], &leaf, 4, &output),
+        INVALID_SCALAR
+    );
+}
+
+#[test]
+fn c_abi_merkle() {
+    let leaves = scalars_to_bytes(&[BlsScalar::one(), BlsScalar::from(2)]);
+    let mut root = [0u8; 32];
+    assert_eq!(poseidon_merkle_root(&leaves, 1, &mut root), SUCCESS);
+
+    let mut output = [0u8; 4 * 32];
+    let code = unsafe {
+        dusk_poseidon_merkle_opening(
+            leaves.as_ptr(),
+            leaves.len(),
+            1,
+            1,
+            output.as_mut_ptr(),
+            output.len(),
+        )
+    };
+    assert_eq!(code, SUCCESS);
+    let mut expected = [0u8; 4 * 32];
+    assert_eq!(
+        poseidon_merkle_opening(&leaves, 1, 1, &mut expected),
+        SUCCESS
+    );
+    assert_eq!(output, expected);
+
+    let code = unsafe {
+        dusk_poseidon_merkle_verify(
+            root.as_ptr(),
+            leaves[32..].as_ptr(),
+            1,
+            output.as_ptr(),
+            output.len(),
+        )
+    };
+    assert_eq!(code, SUCCESS);
+}
+
+#[test]
+fn c_header() {
+    let header = include_str!("../include/dusk_poseidon.h");
+
+    for (name, value) in [
+        ("SUCCESS", SUCCESS),
+        ("INVALID_INPUT_LENGTH", INVALID_INPUT_LENGTH),
+        ("INVALID_OUTPUT_LENGTH", INVALID_OUTPUT_LENGTH),
+        ("INVALID_SCALAR", INVALID_SCALAR),
+        ("INVALID_POINT", INVALID_POINT),
+        ("DECRYPTION_FAILED", DECRYPTION_FAILED),
+        ("INVALID_OPENING", INVALID_OPENING),
+        ("INVALID_POSITION", INVALID_POSITION),
+        ("SCALAR_SIZE", SCALAR_SIZE as u32),
+        ("MAX_INPUT_SCALARS", MAX_INPUT_SCALARS as u32),
+        ("MAX_MERKLE_HEIGHT", MAX_MERKLE_HEIGHT),
+    ] {
+        let define = format!("#define DUSK_POSEIDON_{name} {value}\n");
+        assert!(header.contains(&define), "missing `{define}`");
+    }
+
+    for function in [
+        "dusk_poseidon_hash(",
+        "dusk_poseidon_hash_truncated(",
+        "dusk_poseidon_merkle_root(",
+        "dusk_poseidon_merkle_opening(",
+        "dusk_poseidon_merkle_verify(",
+        "dusk_poseidon_encrypt(",
+        "dusk_poseidon_decrypt(",
+    ] {
+        assert!(header.contains(function), "missing `{function}`");
+    }
+}
+
+#[cfg(feature = "encryption")]
+mod encryption {
+    use super::*;
+
+    use dusk_jubjub::{JubJubAffine, JubJubScalar, GENERATOR_EXTENDED};
+    use dusk_poseidon::encrypt;
+
+    #[test]
+    fn encrypt_decrypt() {
+        let mut rng = StdRng::seed_from_u64(0xbeef);
+        let message: Vec<BlsScalar> =
+            (0..3).map(|_| BlsScalar::random(&mut rng)).collect();
+        let shared_secret: JubJubAffine =
+            (GENERATOR_EXTENDED * JubJubScalar::random(&mut rng)).into();
+        let nonce = BlsScalar::random(&mut rng);
+
+        let mut cipher = vec![0u8; (message.len() + 1) * 32];
+        assert_eq!(
+            poseidon_encrypt(
+                &scalars_to_bytes(&message),
+                &shared_secret.to_bytes(),
+                &nonce.to_bytes(),
+                &mut cipher
+            ),
+            SUCCESS
+        );
+        let expected = encrypt(&message, shared_secret, &nonce.into())
+            .expect("encryption should pass");
+        assert_eq!(cipher, scalars_to_bytes(&expected));
+
+        let mut output = vec![0u8; message.len() * 32];
+        assert_eq!(
+            poseidon_decrypt(
+                &cipher,
+                &shared_secret.to_bytes(),
+                &nonce.to_bytes(),
+                &mut output
+            ),
+            SUCCESS
+        );
+        assert_eq!(output, scalars_to_bytes(&message));
+
+        // encryption fails with an output buffer of the wrong length
+        let mut cipher = vec![0u8; output.len()];
+        assert_eq!(
+            poseidon_encrypt(
+                &output,
+                &shared_secret.to_bytes(),
+                &nonce.to_bytes(),
+                &mut cipher
+            ),
+            INVALID_OUTPUT_LENGTH
+        );
+    }
+
+    #[test]
+    fn c_abi_encryption() {
+        let message = scalars_to_bytes(&[BlsScalar::one(), BlsScalar::from(2)]);
+        let shared_secret = JubJubAffine::from(GENERATOR_EXTENDED).to_bytes();
+        let nonce = BlsScalar::from(42).to_bytes();
+
+        let mut cipher = vec![0u8; message.len() + 32];
+        let code = unsafe {
+            dusk_poseidon_encrypt(
+                message.as_ptr(),
+                message.len(),
+                shared_secret.as_ptr(),
+                nonce.as_ptr(),
+                cipher.as_mut_ptr(),
+                cipher.len(),
+            )
+        };
+        assert_eq!(code, SUCCESS);
+        let mut expected = vec![0u8; cipher.len()];
+        assert_eq!(
+            poseidon_encrypt(&message, &shared_secret, &nonce, &mut expected),
+            SUCCESS
+        );
+        assert_eq!(cipher, expected);
+
+        let mut output = vec![0u8; message.len()];
+        let code = unsafe {
+            dusk_poseidon_decrypt(
+                cipher.as_ptr(),
+                cipher.len(),
+                shared_secret.as_ptr(),
+                nonce.as_ptr(),
+                output.as_mut_ptr(),
+                output.len(),
+            )
+        };
+        assert_eq!(code, SUCCESS);
+        assert_eq!(output, message);
+    }
+
+    #[test]
+    fn decrypt() {
+        let mut rng = StdRng::seed_from_u64(0xbeef);
+        let message: Vec<BlsScalar> =
+            (0..5).map(|_| BlsScalar::random(&mut rng)).collect();
+        let shared_secret: JubJubAffine =
+            (GENERATOR_EXTENDED * JubJubScalar::random(&mut rng)).into();
+        let nonce = BlsScalar::random(&mut rng);
+        let cipher = encrypt(&message, shared_secret, &nonce.into())
+            .expect("encryption should pass");
+        let cipher = scalars_to_bytes(&cipher);
+
+        let mut output = vec![0u8; message.len() * 32];
+        assert_eq!(
+            poseidon_decrypt(
+                &cipher,
+                &shared_secret.to_bytes(),
+                &nonce.to_bytes(),
+                &mut output
+            ),
+            SUCCESS
+        );
+        assert_eq!(output, scalars_to_bytes(&message));
+
+        // decryption fails with the wrong nonce
+        let wrong_nonce = (nonce + BlsScalar::one()).to_bytes();
+        assert_eq!(
+            poseidon_decrypt(
+                &cipher,
+                &shared_secret.to_bytes(),
+                &wrong_nonce,
+                &mut output
+            ),
+            DECRYPTION_FAILED
+        );
+
+        // decryption fails with an output buffer of the wrong length
+        let mut output = vec![0u8; cipher.len()];
+        assert_eq!(
+            poseidon_decrypt(
+                &cipher,
+                &shared_secret.to_bytes(),
+                &nonce.to_bytes(),
+                &mut output
+            ),
+            INVALID_OUTPUT_LENGTH
+        );
+    }
+}
@@ -0,0 +1,37 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#![cfg(feature = "hybrid")]
+
+use dusk_bls12_381::BlsScalar;
+use dusk_poseidon::{Domain, Hash, HybridHasher};
+use ff::Field;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn hybrid_digest() {
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+    let input: Vec<BlsScalar> =
+        (0..7).map(|_| BlsScalar::random(&mut rng)).collect();
+
+    let digest = HybridHasher::digest(Domain::Other, &input);
+
+    // the poseidon digest is the same as the one of the hash
+    assert_eq!(digest.poseidon, Hash::digest(Domain::Other, &input)[0]);
+
+    // updating the input gradually yields the same digests
+    let mut hasher = HybridHasher::new(Domain::Other);
+    hasher.update(&input[..3]).update(&input[3..]);
+    assert_eq!(hasher.finalize(), digest);
+
+    // the blake2b digest commits to the input and the domain
+    let other = HybridHasher::digest(Domain::Other, &input[1..]);
+    assert_ne!(other.blake2b, digest.blake2b);
+    let other = HybridHasher::digest(Domain::Merkle4, &input[..4]);
+    let same_input = HybridHasher::digest(Domain::Other, &input[..4]);
+    assert_ne!(other.blake2b, same_input.blake2b);
+}
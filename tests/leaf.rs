@@ -0,0 +1,147 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bls12_381::BlsScalar;
+use dusk_poseidon::{leaf_hash, Domain, Hash};
+
+#[test]
+fn leaf_hash_encoding() {
+    let scalars = [BlsScalar::from(1), BlsScalar::from(2)];
+
+    assert_eq!(leaf_hash(&scalars, &[3]), leaf_hash(&scalars, &[3]));
+    assert_ne!(leaf_hash(&scalars, &[3]), leaf_hash(&scalars, &[4]));
+
+    // the boundary between scalars and metadata can't be shifted
+    assert_ne!(leaf_hash(&scalars, &[3]), leaf_hash(&scalars[..1], &[2, 3]));
+
+    // the leaf domain is distinct from the other domains
+    assert_ne!(
+        leaf_hash(&scalars, &[]),
+        Hash::digest(Domain::Other, &scalars)[0]
+    );
+}
+
+#[test]
+#[should_panic]
+fn leaf_hash_empty() {
+    leaf_hash(&[], &[]);
+}
+
+#[cfg(feature = "zk")]
+mod gadget {
+    use super::*;
+
+    use dusk_plonk::prelude::Error as PlonkError;
+    use dusk_plonk::prelude::*;
+    use dusk_poseidon::{assert_leaf_hash, leaf_hash_gadget};
+    use once_cell::sync::Lazy;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    static PUB_PARAMS: Lazy<PublicParameters> = Lazy::new(|| {
+        let mut rng = StdRng::seed_from_u64(0xbeef);
+
+        const CAPACITY: usize = 11;
+        PublicParameters::setup(1 << CAPACITY, &mut rng)
+            .expect("Setup of public params should pass")
+    });
+
+    #[derive(Debug, Default)]
+    struct LeafCircuit {
+        scalars: [BlsScalar; 2],
+        meta: [BlsScalar; 2],
+        hash: BlsScalar,
+    }
+
+    impl Circuit for LeafCircuit {
+        fn circuit(&self, composer: &mut Composer) -> Result<(), PlonkError> {
+            let scalars = self.scalars.map(|s| composer.append_witness(s));
+            let meta = self.meta.map(|m| composer.append_witness(m));
+
+            let hash = leaf_hash_gadget(composer, &scalars, &meta);
+            composer.assert_equal_constant(hash, 0, Some(self.hash));
+
+            Ok(())
+        }
+    }
+
+    fn prove_and_verify(circuit: &LeafCircuit) -> Result<(), PlonkError> {
+        let mut rng = StdRng::seed_from_u64(0xbeef);
+
+        let label = b"leaf-tester";
+        let (prover, verifier) =
+            Compiler::compile::<LeafCircuit>(&PUB_PARAMS, label)?;
+
+        let (proof, public_inputs) = prover.prove(&mut rng, circuit)?;
+        verifier.verify(&proof, &public_inputs)
+    }
+
+    #[test]
+    fn leaf_hash_gadget_matches() -> Result<(), PlonkError> {
+        let scalars = [BlsScalar::from(1), BlsScalar::from(2)];
+        let meta = [42, u64::MAX];
+
+        let circuit = LeafCircuit {
+            scalars,
+            meta: meta.map(BlsScalar::from),
+            hash: leaf_hash(&scalars, &meta),
+        };
+        prove_and_verify(&circuit)?;
+
+        // a different metadata field fails
+        let circuit = LeafCircuit {
+            meta: [BlsScalar::from(43), BlsScalar::from(u64::MAX)],
+            ..circuit
+        };
+        assert!(prove_and_verify(&circuit).is_err());
+
+        Ok(())
+    }
+
+    #[derive(Debug, Default)]
+    struct AssertLeafCircuit {
+        fields: [BlsScalar; 3],
+        leaf: BlsScalar,
+    }
+
+    impl Circuit for AssertLeafCircuit {
+        fn circuit(&self, composer: &mut Composer) -> Result<(), PlonkError> {
+            let fields = self.fields.map(|f| composer.append_witness(f));
+            let leaf = composer.append_witness(self.leaf);
+
+            assert_leaf_hash(composer, &fields, leaf);
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn assert_leaf_hash_matches() -> Result<(), PlonkError> {
+        let mut rng = StdRng::seed_from_u64(0xbeef);
+
+        let label = b"leaf-tester";
+        let (prover, verifier) =
+            Compiler::compile::<AssertLeafCircuit>(&PUB_PARAMS, label)?;
+
+        let fields =
+            [BlsScalar::from(1), BlsScalar::from(2), BlsScalar::from(3)];
+        let circuit = AssertLeafCircuit {
+            fields,
+            leaf: leaf_hash(&fields, &[]),
+        };
+        let (proof, public_inputs) = prover.prove(&mut rng, &circuit)?;
+        verifier.verify(&proof, &public_inputs)?;
+
+        // a leaf hashed in another domain fails
+        let circuit = AssertLeafCircuit {
+            leaf: Hash::digest(Domain::Other, &fields)[0],
+            ..circuit
+        };
+        assert!(prover.prove(&mut rng, &circuit).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bls12_381::BlsScalar;
+use dusk_poseidon::level_hash;
+use dusk_poseidon::merkle::{opening, root_from_leaves, verify_opening};
+
+// Compute the root by materializing every level of the tree.
+fn root<const A: usize>(height: usize, leaves: &[BlsScalar]) -> BlsScalar {
+    let mut level = leaves.to_vec();
+    for _ in 0..height {
+        level = level
+            .chunks(A)
+            .map(|chunk| {
+                let mut nodes = [BlsScalar::zero(); A];
+                nodes[..chunk.len()].copy_from_slice(chunk);
+                level_hash(&nodes)
+            })
+            .collect();
+    }
+    level.first().copied().unwrap_or(BlsScalar::zero())
+}
+
+#[test]
+fn root_from_leaves_matches() {
+    let leaves: Vec<BlsScalar> = (0..64u64).map(BlsScalar::from).collect();
+
+    // empty, single leaf, partial and full trees
+    for len in [0, 1, 3, 5, 16, 17, 63, 64] {
+        let leaves = &leaves[..len];
+        assert_eq!(
+            root_from_leaves::<2>(6, leaves.iter().copied()),
+            root::<2>(6, leaves)
+        );
+        assert_eq!(
+            root_from_leaves::<4>(3, leaves.iter().copied()),
+            root::<4>(3, leaves)
+        );
+    }
+
+    // a tree of height zero is its only leaf
+    assert_eq!(root_from_leaves::<2>(0, [leaves[7]]), leaves[7]);
+}
+
+#[test]
+#[should_panic]
+fn root_from_too_many_leaves() {
+    root_from_leaves::<4>(1, (0..5u64).map(BlsScalar::from));
+}
+
+#[test]
+fn opening_verifies() {
+    let leaves: Vec<BlsScalar> = (0..20u64).map(BlsScalar::from).collect();
+
+    let root = root_from_leaves::<4>(3, leaves.iter().copied());
+    for (position, leaf) in leaves.iter().enumerate() {
+        let position = position as u64;
+        let path = opening::<4>(3, leaves.iter().copied(), position);
+        assert_eq!(path.len(), 3);
+        assert!(verify_opening(&root, leaf, position, &path));
+
+        // the opening doesn't verify another leaf or position
+        assert!(!verify_opening(
+            &root,
+            &(leaf + BlsScalar::one()),
+            position,
+            &path
+        ));
+        assert!(!verify_opening(&root, leaf, position + 1, &path));
+        assert!(!verify_opening(&root, leaf, position + 64, &path));
+    }
+
+    let root = root_from_leaves::<2>(5, leaves.iter().copied());
+    let path = opening::<2>(5, leaves.iter().copied(), 17);
+    assert!(verify_opening(&root, &leaves[17], 17, &path));
+
+    // a tree of height zero is opened by its only leaf
+    let path = opening::<2>(0, [leaves[7]], 0);
+    assert!(verify_opening(&leaves[7], &leaves[7], 0, &path));
+    assert!(!verify_opening(&leaves[7], &leaves[7], 1, &path));
+}
+
+#[test]
+#[should_panic]
+fn opening_of_empty_position() {
+    opening::<4>(2, (0..5u64).map(BlsScalar::from), 5);
+}
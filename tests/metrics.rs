@@ -0,0 +1,92 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#![cfg(feature = "metrics")]
+
+use dusk_bls12_381::BlsScalar;
+use dusk_poseidon::metrics::{self, Counters, Metrics, MetricsSink};
+use dusk_poseidon::{compress, hash_rows, level_hash, Domain, Hash};
+
+#[test]
+fn counters() {
+    let counters = Counters::new();
+
+    let input = [BlsScalar::one(); 9];
+    let mut hash = Hash::new(Domain::Other);
+    hash.update(&input);
+    let permutations = hash.permutation_count() as u64;
+    assert_eq!(hash.finalize_metered(&counters), hash.finalize());
+
+    let m = counters.snapshot();
+    assert_eq!(m.permutations, permutations);
+    assert_eq!(m.hashes, 1);
+    assert_eq!(m.levels, 0);
+    assert_eq!(m.tag_cache_hits, 0);
+
+    let level = [BlsScalar::one(); 4];
+    assert_eq!(metrics::level_hash(&level, &counters), level_hash(&level));
+    let rows = [[BlsScalar::one(); 2]; 3];
+    assert_eq!(
+        metrics::hash_rows(Domain::Other, &rows, &counters),
+        hash_rows(Domain::Other, &rows)
+    );
+    let (a, b) = (BlsScalar::one(), BlsScalar::from(2));
+    assert_eq!(metrics::compress(&a, &b, &counters), compress(&a, &b));
+
+    let m = counters.reset();
+    assert_eq!(m.permutations, permutations + 5);
+    assert_eq!(m.hashes, 6);
+    assert_eq!(m.levels, 1);
+    assert_eq!(m.tag_cache_hits, 2);
+
+    assert_eq!(counters.snapshot(), Metrics::default());
+}
+
+#[test]
+fn unmetered_calls_are_not_counted() {
+    let counters = Counters::new();
+
+    Hash::digest(Domain::Other, &[BlsScalar::one(); 3]);
+    level_hash(&[BlsScalar::one(); 2]);
+
+    assert_eq!(counters.snapshot(), Metrics::default());
+}
+
+// A sink only interested in the hashes.
+#[derive(Default)]
+struct HashCount(core::cell::Cell<u64>);
+
+impl MetricsSink for HashCount {
+    fn record_hash(&self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+#[test]
+fn custom_sink() {
+    let sink = HashCount::default();
+
+    metrics::hash_rows(Domain::Other, &[[BlsScalar::one(); 5]; 4], &sink);
+
+    assert_eq!(sink.0.get(), 4);
+}
+
+#[cfg(target_has_atomic = "64")]
+#[test]
+fn atomic_counters() {
+    static COUNTERS: metrics::AtomicCounters = metrics::AtomicCounters::new();
+
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| metrics::level_hash(&[BlsScalar::one(); 4], &COUNTERS));
+        }
+    });
+
+    let m = COUNTERS.reset();
+    assert_eq!(m.levels, 4);
+    assert_eq!(m.hashes, 4);
+    assert_eq!(m.permutations, 4);
+}
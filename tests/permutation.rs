@@ -0,0 +1,120 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#![cfg(feature = "zk")]
+
+use dusk_plonk::prelude::Error as PlonkError;
+use dusk_plonk::prelude::*;
+use dusk_poseidon::{
+    BackendPermutation, Domain, GadgetBackend, GadgetPermutation, Hash,
+    HashGadget, ScalarPermutation, HADES_WIDTH,
+};
+use dusk_safe::{Call, Safe, Sponge};
+use ff::Field;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+const IO_PATTERN: [Call; 2] = [Call::Absorb(3), Call::Squeeze(1)];
+
+#[test]
+fn custom_sponge() {
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+    let input = [
+        BlsScalar::random(&mut rng),
+        BlsScalar::random(&mut rng),
+        BlsScalar::random(&mut rng),
+    ];
+
+    let mut sponge = Sponge::start(
+        ScalarPermutation::new(),
+        IO_PATTERN,
+        Domain::Other.into(),
+    )
+    .expect("io-pattern should be valid");
+    sponge.absorb(3, input).expect("absorbing should succeed");
+    sponge.squeeze(1).expect("squeezing should succeed");
+    let output = sponge.finish().expect("io-pattern should be followed");
+
+    assert_eq!(output, Hash::digest(Domain::Other, &input));
+}
+
+#[test]
+fn scalar_permutation_is_zero_sized() {
+    assert_eq!(core::mem::size_of::<ScalarPermutation>(), 0);
+}
+
+#[test]
+fn permutation() {
+    let mut state = [BlsScalar::one(); HADES_WIDTH];
+    ScalarPermutation::new().permute(&mut state);
+
+    assert_ne!(state, [BlsScalar::one(); HADES_WIDTH]);
+}
+
+#[derive(Debug, Default)]
+struct SpongeCircuit {
+    input: [BlsScalar; 3],
+    output: BlsScalar,
+}
+
+impl Circuit for SpongeCircuit {
+    fn circuit(&self, composer: &mut Composer) -> Result<(), PlonkError> {
+        let input = self.input.map(|s| composer.append_witness(s));
+
+        let mut sponge = Sponge::start(
+            GadgetPermutation::new(composer),
+            IO_PATTERN,
+            Domain::Other.into(),
+        )
+        .expect("io-pattern should be valid");
+        sponge.absorb(3, input).expect("absorbing should succeed");
+        sponge.squeeze(1).expect("squeezing should succeed");
+        let output = sponge.finish().expect("io-pattern should be followed");
+
+        composer.assert_equal_constant(output[0], 0, Some(self.output));
+
+        Ok(())
+    }
+}
+
+#[test]
+fn custom_sponge_gadget() -> Result<(), PlonkError> {
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+    let pp = PublicParameters::setup(1 << 11, &mut rng)?;
+    let (prover, verifier) =
+        Compiler::compile::<SpongeCircuit>(&pp, b"custom-sponge")?;
+
+    let input = [
+        BlsScalar::random(&mut rng),
+        BlsScalar::random(&mut rng),
+        BlsScalar::random(&mut rng),
+    ];
+    let output = Hash::digest(Domain::Other, &input)[0];
+    let circuit = SpongeCircuit { input, output };
+
+    let (proof, public_inputs) = prover.prove(&mut rng, &circuit)?;
+    verifier.verify(&proof, &public_inputs)
+}
+
+#[test]
+fn backend_permutation() {
+    let mut composer = Composer::initialized();
+    let input = [BlsScalar::one(), BlsScalar::from(2), BlsScalar::from(3)];
+    let input = input.map(|s| composer.append_witness(s));
+
+    let mut hash = HashGadget::new(Domain::Other);
+    hash.update(&input);
+    let expected = hash.finalize(&mut composer);
+
+    let output = hash
+        .finalize_with(BackendPermutation::<GadgetBackend>::new(&mut composer));
+
+    assert_eq!(composer[output[0]], composer[expected[0]]);
+    assert_eq!(
+        composer[output[0]],
+        Hash::digest(Domain::Other, &input.map(|w| composer[w]))[0]
+    );
+}
@@ -0,0 +1,131 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bls12_381::BlsScalar;
+use dusk_poseidon::{position_of, Domain, Hash};
+use ff::Field;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn position_bounds() {
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+
+    for _ in 0..100 {
+        let key = BlsScalar::random(&mut rng);
+
+        let position = position_of::<_, 4>(Domain::Other, &key, 17);
+        assert!(position < 1 << 34);
+
+        let position = position_of::<_, 2>(Domain::Other, &key, 3);
+        assert!(position < 1 << 3);
+    }
+}
+
+#[test]
+fn position_from_truncated_hash() {
+    let key = BlsScalar::from(42);
+
+    let hash = Hash::digest_truncated(Domain::Other, &[key])[0].to_bytes();
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&hash[..8]);
+    let expected = u64::from_le_bytes(bytes);
+
+    assert_eq!(position_of::<_, 2>(Domain::Other, &key, 64), expected);
+    assert_eq!(
+        position_of::<_, 4>(Domain::Other, &key, 16),
+        expected & 0xffff_ffff
+    );
+}
+
+#[test]
+#[should_panic]
+fn position_arity_not_power_of_two() {
+    position_of::<_, 3>(Domain::Other, &BlsScalar::one(), 2);
+}
+
+#[test]
+#[should_panic]
+fn position_too_many_bits() {
+    position_of::<_, 4>(Domain::Other, &BlsScalar::one(), 33);
+}
+
+#[cfg(feature = "zk")]
+mod gadget {
+    use super::*;
+
+    use dusk_plonk::prelude::Error as PlonkError;
+    use dusk_plonk::prelude::*;
+    use dusk_poseidon::position_of_gadget;
+    use once_cell::sync::Lazy;
+
+    static PUB_PARAMS: Lazy<PublicParameters> = Lazy::new(|| {
+        let mut rng = StdRng::seed_from_u64(0xbeef);
+
+        const CAPACITY: usize = 12;
+        PublicParameters::setup(1 << CAPACITY, &mut rng)
+            .expect("Setup of public params should pass")
+    });
+
+    #[derive(Debug, Default)]
+    struct PositionCircuit<const A: usize, const H: usize> {
+        key: BlsScalar,
+        position: BlsScalar,
+    }
+
+    impl<const A: usize, const H: usize> Circuit for PositionCircuit<A, H> {
+        fn circuit(&self, composer: &mut Composer) -> Result<(), PlonkError> {
+            let key = composer.append_witness(self.key);
+            let position = composer.append_witness(self.position);
+
+            let derived =
+                position_of_gadget::<_, A>(composer, Domain::Other, key, H);
+            composer.assert_equal(position, derived);
+
+            Ok(())
+        }
+    }
+
+    fn prove_and_verify<const A: usize, const H: usize>(
+        key: BlsScalar,
+        position: u64,
+    ) -> Result<(), PlonkError> {
+        let mut rng = StdRng::seed_from_u64(0xbeef);
+
+        let label = b"position-tester";
+        let (prover, verifier) =
+            Compiler::compile::<PositionCircuit<A, H>>(&PUB_PARAMS, label)?;
+
+        let circuit = PositionCircuit::<A, H> {
+            key,
+            position: BlsScalar::from(position),
+        };
+        let (proof, public_inputs) = prover.prove(&mut rng, &circuit)?;
+
+        verifier.verify(&proof, &public_inputs)
+    }
+
+    #[test]
+    fn position_gadget() -> Result<(), PlonkError> {
+        let mut rng = StdRng::seed_from_u64(0xbeef);
+        let key = BlsScalar::random(&mut rng);
+
+        let position = position_of::<_, 4>(Domain::Other, &key, 17);
+        prove_and_verify::<4, 17>(key, position)?;
+
+        let position = position_of::<_, 2>(Domain::Other, &key, 64);
+        prove_and_verify::<2, 64>(key, position)
+    }
+
+    #[test]
+    fn position_gadget_wrong_position() {
+        let mut rng = StdRng::seed_from_u64(0xbeef);
+        let key = BlsScalar::random(&mut rng);
+
+        let position = position_of::<_, 4>(Domain::Other, &key, 17);
+        assert!(prove_and_verify::<4, 17>(key, position ^ 1).is_err());
+    }
+}
@@ -0,0 +1,57 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use dusk_bls12_381::BlsScalar;
+use dusk_poseidon::is_reserved_encoding;
+use dusk_poseidon::pset::PoseidonSet;
+
+#[test]
+fn insert_and_query() {
+    let mut set = PoseidonSet::new(1 << 12, 4);
+    let items: Vec<[BlsScalar; 2]> = (0..100u64)
+        .map(|i| [BlsScalar::from(i), BlsScalar::from(i + 1)])
+        .collect();
+
+    items.iter().for_each(|item| set.insert(item));
+
+    // inserted items are never missed
+    assert!(items.iter().all(|item| set.maybe_contains(item)));
+
+    // with a low load, most other items are reported as missing
+    let missing = (1000..1100u64)
+        .filter(|i| !set.maybe_contains(&[BlsScalar::from(*i)]))
+        .count();
+    assert!(missing > 90);
+
+    // the empty item is an item as any other
+    assert!(!set.maybe_contains(&[]));
+    set.insert(&[]);
+    assert!(set.maybe_contains(&[]));
+}
+
+#[test]
+fn root() {
+    let mut set = PoseidonSet::new(100, 3);
+    let empty = set.root();
+
+    set.insert(&[BlsScalar::one()]);
+    assert_ne!(set.root(), empty);
+
+    // the root commits to the parameters of the set
+    assert_eq!(PoseidonSet::new(100, 3).root(), empty);
+    assert_ne!(PoseidonSet::new(101, 3).root(), empty);
+    assert_ne!(PoseidonSet::new(100, 2).root(), empty);
+
+    // the domains of the set are reserved
+    assert!(is_reserved_encoding(1 << 35));
+    assert!(is_reserved_encoding((1 << 35) + 1));
+}
+
+#[test]
+#[should_panic]
+fn no_bits() {
+    PoseidonSet::new(0, 1);
+}
@@ -0,0 +1,126 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#![cfg(feature = "zk")]
+
+use once_cell::sync::Lazy;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use dusk_plonk::prelude::Error as PlonkError;
+use dusk_plonk::prelude::*;
+use dusk_poseidon::{
+    assert_range_committed, is_reserved_encoding, range_commitment,
+};
+
+static PUB_PARAMS: Lazy<PublicParameters> = Lazy::new(|| {
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+
+    const CAPACITY: usize = 13;
+    PublicParameters::setup(1 << CAPACITY, &mut rng)
+        .expect("Setup of public params should pass")
+});
+
+const BLINDER: BlsScalar = BlsScalar::from_raw([0xb1, 0x1d, 0xe4, 0]);
+
+#[derive(Debug, Default)]
+struct RangeCircuit<const BITS: usize> {
+    value: BlsScalar,
+    blinder: BlsScalar,
+    commitment: BlsScalar,
+}
+
+impl<const BITS: usize> Circuit for RangeCircuit<BITS> {
+    fn circuit(&self, composer: &mut Composer) -> Result<(), PlonkError> {
+        let value = composer.append_witness(self.value);
+        let blinder = composer.append_witness(self.blinder);
+        assert_range_committed(composer, value, blinder, BITS, self.commitment);
+
+        Ok(())
+    }
+}
+
+fn prove_and_verify<const BITS: usize>(
+    value: BlsScalar,
+    blinder: BlsScalar,
+    commitment: BlsScalar,
+) -> Result<(), PlonkError> {
+    let mut rng = StdRng::seed_from_u64(0xbeef);
+
+    let label = b"range-tester";
+    let (prover, verifier) =
+        Compiler::compile::<RangeCircuit<BITS>>(&PUB_PARAMS, label)?;
+
+    let circuit = RangeCircuit::<BITS> {
+        value,
+        blinder,
+        commitment,
+    };
+    let (proof, public_inputs) = prover.prove(&mut rng, &circuit)?;
+
+    verifier.verify(&proof, &public_inputs)?;
+    assert_eq!(public_inputs, [commitment]);
+
+    Ok(())
+}
+
+#[test]
+fn range_committed() -> Result<(), PlonkError> {
+    let value = BlsScalar::from(0xdead_beef_u64);
+
+    let commitment = range_commitment(&value, &BLINDER, 32)
+        .expect("the value should fit 32 bits");
+    prove_and_verify::<32>(value, BLINDER, commitment)?;
+
+    // bits that are not a multiple of the digit size
+    let commitment = range_commitment(&value, &BLINDER, 35)
+        .expect("the value should fit 35 bits");
+    prove_and_verify::<35>(value, BLINDER, commitment)?;
+
+    let max = BlsScalar::from(u64::MAX);
+    let commitment = range_commitment(&max, &BLINDER, 64)
+        .expect("the value should fit 64 bits");
+    prove_and_verify::<64>(max, BLINDER, commitment)
+}
+
+#[test]
+fn range_out_of_bounds() {
+    let value = BlsScalar::from(1u64 << 32);
+
+    assert!(range_commitment(&value, &BLINDER, 32).is_none());
+
+    // commit to the truncated digits which the prover can't recompose
+    let truncated = range_commitment(&BlsScalar::zero(), &BLINDER, 32)
+        .expect("zero should fit 32 bits");
+    assert!(prove_and_verify::<32>(value, BLINDER, truncated).is_err());
+}
+
+#[test]
+fn range_wrong_commitment() {
+    let value = BlsScalar::from(42u64);
+    let other = range_commitment(&BlsScalar::from(43u64), &BLINDER, 16)
+        .expect("the value should fit 16 bits");
+
+    assert!(prove_and_verify::<16>(value, BLINDER, other).is_err());
+}
+
+#[test]
+fn range_blinded() {
+    let value = BlsScalar::from(42u64);
+    let commitment = range_commitment(&value, &BLINDER, 16)
+        .expect("the value should fit 16 bits");
+
+    // the blinder hides the value and is needed to open the commitment
+    let other_blinder = BLINDER + BlsScalar::one();
+    assert_ne!(
+        Some(commitment),
+        range_commitment(&value, &other_blinder, 16)
+    );
+    assert!(prove_and_verify::<16>(value, other_blinder, commitment).is_err());
+
+    // the commitment is in its own domain
+    assert!(is_reserved_encoding(1 << 39));
+}
@@ -0,0 +1,49 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#![cfg(feature = "std")]
+
+use std::io::{Cursor, Seek, SeekFrom};
+
+use dusk_bls12_381::BlsScalar;
+use dusk_poseidon::convert::packed_bytes_to_scalars;
+use dusk_poseidon::{Domain, Hash};
+
+fn digest_bytes(bytes: &[u8]) -> Vec<BlsScalar> {
+    let mut input = vec![BlsScalar::from(bytes.len() as u64)];
+    input.extend(packed_bytes_to_scalars(bytes));
+    Hash::digest(Domain::Other, &input)
+}
+
+#[test]
+fn digest_reader() {
+    // empty, a single scalar, a partial scalar and several chunks
+    for len in [0, 31, 100, 100_000] {
+        let bytes: Vec<u8> = (0..len).map(|i| i as u8).collect();
+
+        let digest = Hash::digest_reader(Domain::Other, Cursor::new(&bytes))
+            .expect("reading from memory should succeed");
+        assert_eq!(digest, digest_bytes(&bytes));
+    }
+}
+
+#[test]
+fn digest_reader_position() {
+    let bytes = [0xab; 1000];
+
+    // only the bytes after the current position are digested
+    let mut reader = Cursor::new(&bytes);
+    reader.seek(SeekFrom::Start(10)).unwrap();
+    let digest = Hash::digest_reader(Domain::Other, reader)
+        .expect("reading from memory should succeed");
+    assert_eq!(digest, digest_bytes(&bytes[10..]));
+}
+
+#[test]
+#[should_panic]
+fn digest_reader_domain() {
+    let _ = Hash::digest_reader(Domain::Merkle4, Cursor::new([0u8; 31]));
+}
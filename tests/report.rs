@@ -0,0 +1,52 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#![cfg(feature = "zk")]
+
+use dusk_poseidon::report::{
+    permutation_constraints, report, Gadget, REPORT_INPUT_LENGTHS,
+};
+use dusk_poseidon::{GadgetBackend, GadgetPermutation, HadesCircuitBackend};
+use dusk_safe::Safe;
+
+// Changes to these numbers indicate a change of the circuit description of
+// the gadgets and need to be documented in the changelog.
+#[test]
+fn constraints() {
+    assert_eq!(Gadget::Hash(4).constraints(), 990);
+    assert_eq!(Gadget::Hash(5).constraints(), 1976);
+    assert_eq!(Gadget::HashTruncated(4).constraints(), 1770);
+}
+
+#[test]
+fn hades_permutation_constraints() {
+    let constraints = permutation_constraints(|composer, state| {
+        GadgetPermutation::new(composer).permute(state);
+    });
+    assert_eq!(constraints, 985);
+    assert_eq!(permutation_constraints(GadgetBackend::permute), constraints);
+}
+
+#[cfg(feature = "encryption")]
+#[test]
+fn encryption_constraints() {
+    assert_eq!(Gadget::Encrypt(4).constraints(), 1982);
+    assert_eq!(Gadget::Decrypt(4).constraints(), 1983);
+}
+
+#[test]
+fn report_all_gadgets() {
+    let report = report();
+
+    let gadgets_per_len = match cfg!(feature = "encryption") {
+        true => 4,
+        false => 2,
+    };
+    assert_eq!(report.len(), REPORT_INPUT_LENGTHS.len() * gadgets_per_len);
+    report.iter().for_each(|(gadget, constraints)| {
+        assert_eq!(gadget.constraints(), *constraints)
+    });
+}